@@ -0,0 +1,43 @@
+//! `yearfrac_many` against a naive loop of `yearfrac` calls, over cashflow-like periods
+//! and long ActAct spans. Run with `cargo bench --bench yearfrac_many`.
+use chrono::{Duration, NaiveDate};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use yearfrac::DayCountConvention;
+
+fn pairs(count: usize, span_days: i64) -> Vec<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
+    (0..count as i64)
+        .map(|k| {
+            let start = first + Duration::days(k * 7);
+            (start, start + Duration::days(span_days + k % 400))
+        })
+        .collect()
+}
+
+fn bench_yearfrac_many(c: &mut Criterion) {
+    for (name, pairs) in [
+        ("periods", pairs(10_000, 180)),
+        ("long spans", pairs(10_000, 30 * 365)),
+    ] {
+        for conv in [DayCountConvention::ActAct, DayCountConvention::US30360] {
+            let mut group = c.benchmark_group(format!("{conv} {name}"));
+            group.bench_function("yearfrac_many", |b| {
+                b.iter(|| conv.yearfrac_many(black_box(&pairs)))
+            });
+            group.bench_function("naive loop", |b| {
+                b.iter(|| {
+                    let mut out = Vec::new();
+                    for (start, end) in black_box(&pairs) {
+                        out.push(conv.yearfrac(*start, *end));
+                    }
+                    out
+                })
+            });
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, bench_yearfrac_many);
+criterion_main!(benches);
@@ -0,0 +1,259 @@
+use chrono::NaiveDate;
+
+use crate::{check_finite, DayCountConvention, DayCountConventionError};
+
+impl DayCountConvention {
+    /// Accrued interest on `face` at `coupon_rate` from `start` (usually the last coupon date) to `settlement`,
+    /// i.e. `face * coupon_rate * yearfrac(start, settlement)`.
+    /// `coupon_rate` is annual: for a semiannual bond it is not halved.
+    /// # Examples
+    /// A 5% semiannual bond paying on Jan 15 and Jul 15, settled three months into the period:
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let last_coupon = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let settlement = NaiveDate::from_ymd_opt(2021, 4, 15).unwrap();
+    /// let ai = DayCountConvention::US30360
+    ///     .accrued_interest(last_coupon, settlement, 0.05, 100.0)
+    ///     .unwrap();
+    /// // half of the 2.5 semiannual coupon
+    /// assert!((ai - 1.25).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `coupon_rate` or `face` is NaN or infinite.
+    pub fn accrued_interest(
+        &self,
+        start: NaiveDate,
+        settlement: NaiveDate,
+        coupon_rate: f64,
+        face: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("coupon_rate", coupon_rate)?;
+        check_finite("face", face)?;
+        Ok(face * coupon_rate * self.yearfrac(start, settlement))
+    }
+
+    /// Flat annual rate which accrues `accrued` on `notional` between `start` and `end`.
+    /// Inverse of [`DayCountConvention::accrued_interest`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 4, 15).unwrap();
+    /// let rate = DayCountConvention::US30360.implied_rate(start, end, 100.0, 1.25).unwrap();
+    /// assert!((rate - 0.05).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `notional` or `accrued` is NaN or infinite,
+    /// `InvalidInput` if the year fraction or the notional is zero.
+    pub fn implied_rate(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        notional: f64,
+        accrued: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("notional", notional)?;
+        check_finite("accrued", accrued)?;
+        let yf = self.yearfrac(start, end);
+        if yf == 0.0 {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "year fraction is zero",
+            });
+        }
+        if notional == 0.0 {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "notional is zero",
+            });
+        }
+        Ok(accrued / (notional * yf))
+    }
+
+    /// Accrued interest over a schedule of `(start, end, is_stub)` periods where
+    /// regular periods use `regular_conv` and stub periods use `stub_conv`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let schedule = [
+    ///     (NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(), true),
+    ///     (NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), false),
+    /// ];
+    /// let ai = DayCountConvention::accrual_with_stub_convention(
+    ///     DayCountConvention::US30360,
+    ///     DayCountConvention::Act365,
+    ///     &schedule,
+    ///     100.0,
+    ///     0.05,
+    /// )
+    /// .unwrap();
+    /// assert!((ai - (100.0 * 0.05 * 122.0 / 365.0 + 2.5)).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `notional` or `rate` is NaN or infinite.
+    pub fn accrual_with_stub_convention(
+        regular_conv: DayCountConvention,
+        stub_conv: DayCountConvention,
+        schedule: &[(NaiveDate, NaiveDate, bool)],
+        notional: f64,
+        rate: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("notional", notional)?;
+        check_finite("rate", rate)?;
+        schedule
+            .iter()
+            .map(|(start, end, is_stub)| {
+                let conv = if *is_stub { stub_conv } else { regular_conv };
+                conv.accrued_interest(*start, *end, rate, notional)
+            })
+            .sum()
+    }
+
+    /// Total accrued interest over `(start, end, notional, rate)` positions under this convention.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let total = DayCountConvention::US30360
+    ///     .portfolio_accrual(&[(start, end, 100.0, 0.02), (start, end, 200.0, 0.01)])
+    ///     .unwrap();
+    /// assert!((total - 2.0).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if any notional or rate is NaN or infinite.
+    pub fn portfolio_accrual(
+        &self,
+        positions: &[(NaiveDate, NaiveDate, f64, f64)],
+    ) -> Result<f64, DayCountConventionError> {
+        positions
+            .iter()
+            .map(|(start, end, notional, rate)| {
+                self.accrued_interest(*start, *end, *rate, *notional)
+            })
+            .sum()
+    }
+
+    /// Notional-weighted average year fraction across trades using different conventions,
+    /// `sum(w_i * conv_i.yearfrac(start, end)) / sum(w_i)`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+    /// let yf = DayCountConvention::notional_weighted_fraction(
+    ///     start,
+    ///     end,
+    ///     &[(DayCountConvention::Act360, 50.0), (DayCountConvention::Act365, 50.0)],
+    /// )
+    /// .unwrap();
+    /// assert!((yf - (90.0 / 360.0 + 90.0 / 365.0) / 2.0).abs() < 1e-12);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` on a NaN or infinite weight, `InvalidInput` if the weights sum to zero.
+    pub fn notional_weighted_fraction(
+        start: NaiveDate,
+        end: NaiveDate,
+        weighted: &[(DayCountConvention, f64)],
+    ) -> Result<f64, DayCountConventionError> {
+        let (mut weighted_sum, mut total) = (0.0, 0.0);
+        for (conv, weight) in weighted {
+            check_finite("weight", *weight)?;
+            weighted_sum += weight * conv.yearfrac(start, end);
+            total += weight;
+        }
+        if total == 0.0 {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "weights sum to zero",
+            });
+        }
+        Ok(weighted_sum / total)
+    }
+
+    /// Accrued interest split at `handover` between seller (`[start, handover]`) and
+    /// buyer (`[handover, end]`).
+    /// For Act/360 and Act/365 the two parts add up to the accrual of the whole period.
+    /// ActAct and US 30/360 are not additive, so the parts may differ slightly from it:
+    /// e.g. under US 30/360 Jan 15 - Jan 31 - Feb 15 gives 16 + 15 days, not 30.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let handover = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let (seller, buyer) = DayCountConvention::US30360
+    ///     .split_accrual(start, handover, end, 100.0, 0.04)
+    ///     .unwrap();
+    /// assert!((seller - 1.0).abs() < 1e-9);
+    /// assert!((buyer - 1.0).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `InvalidInput` unless `start <= handover <= end`,
+    /// `NonFiniteInput` if `notional` or `rate` is NaN or infinite.
+    pub fn split_accrual(
+        &self,
+        start: NaiveDate,
+        handover: NaiveDate,
+        end: NaiveDate,
+        notional: f64,
+        rate: f64,
+    ) -> Result<(f64, f64), DayCountConventionError> {
+        if !(start <= handover && handover <= end) {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "split_accrual needs start <= handover <= end",
+            });
+        }
+        Ok((
+            self.accrued_interest(start, handover, rate, notional)?,
+            self.accrued_interest(handover, end, rate, notional)?,
+        ))
+    }
+
+    /// Accrued interest for a bond with an ex-dividend period.
+    /// Settling before `ex_div_date` accrues normally from `last_coupon` to `settlement`.
+    /// On or after it the buyer will not receive the next coupon, so the accrual is negative:
+    /// minus the interest from `settlement` to `next_coupon`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let d = |m, d| NaiveDate::from_ymd_opt(2021, m, d).unwrap();
+    /// let ai = DayCountConvention::US30360
+    ///     .accrual_with_ex_div(d(1, 1), d(6, 21), d(7, 1), d(6, 20), 100.0, 0.036)
+    ///     .unwrap();
+    /// assert!((ai + 0.1).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `InvalidInput` unless `last_coupon <= settlement <= next_coupon` and
+    /// `last_coupon <= ex_div_date <= next_coupon`,
+    /// `NonFiniteInput` if `notional` or `rate` is NaN or infinite.
+    pub fn accrual_with_ex_div(
+        &self,
+        last_coupon: NaiveDate,
+        settlement: NaiveDate,
+        next_coupon: NaiveDate,
+        ex_div_date: NaiveDate,
+        notional: f64,
+        rate: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        if !(last_coupon <= settlement && settlement <= next_coupon) {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "accrual_with_ex_div needs last_coupon <= settlement <= next_coupon",
+            });
+        }
+        if !(last_coupon <= ex_div_date && ex_div_date <= next_coupon) {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "accrual_with_ex_div needs last_coupon <= ex_div_date <= next_coupon",
+            });
+        }
+        if settlement < ex_div_date {
+            self.accrued_interest(last_coupon, settlement, rate, notional)
+        } else {
+            Ok(-self.accrued_interest(settlement, next_coupon, rate, notional)?)
+        }
+    }
+}
@@ -0,0 +1,526 @@
+use chrono::{Datelike, NaiveDate};
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::calendar::is_weekend;
+use crate::{float, ConventionFamily, DayCountConvention};
+
+impl DayCountConvention {
+    /// Time-weighted (trapezoidal) average of a series of `(date, value)` samples,
+    /// where each segment is weighted by its year fraction under this convention.
+    /// Commonly used for average-rate options.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let samples = [
+    ///     (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 1.0),
+    ///     (NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(), 3.0),
+    /// ];
+    /// let avg = DayCountConvention::US30360.time_weighted_average(&samples);
+    /// assert!((avg - 2.0).abs() < 1e-9);
+    /// ```
+    /// # Panics
+    /// If there are fewer than two samples or the dates are not strictly increasing.
+    pub fn time_weighted_average(&self, samples: &[(NaiveDate, f64)]) -> f64 {
+        assert!(
+            samples.len() >= 2,
+            "yearfrac: time_weighted_average needs at least two samples"
+        );
+        assert!(
+            samples.windows(2).all(|w| w[0].0 < w[1].0),
+            "yearfrac: time_weighted_average needs strictly increasing dates"
+        );
+        let (mut area, mut total) = (0.0, 0.0);
+        for w in samples.windows(2) {
+            let ((d0, v0), (d1, v1)) = (w[0], w[1]);
+            let yf = self.yearfrac(d0, d1);
+            area += 0.5 * (v0 + v1) * yf;
+            total += yf;
+        }
+        area / total
+    }
+
+    /// Year fraction from `anchor` to each of `dates`, i.e. the time axis of a curve.
+    /// Monotonic if `dates` are sorted and on or after `anchor`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let anchor = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let dates = [
+    ///     NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    /// ];
+    /// let ts = DayCountConvention::US30360.cumulative_fractions(anchor, &dates);
+    /// assert_eq!(ts, vec![0.5, 1.0]);
+    /// ```
+    pub fn cumulative_fractions(&self, anchor: NaiveDate, dates: &[NaiveDate]) -> Vec<f64> {
+        dates.iter().map(|d| self.yearfrac(anchor, *d)).collect()
+    }
+
+    /// Year fraction from the first of `dates` to each of them, starting at 0.0, e.g. the
+    /// time axis of a cashflow schedule. Each value is a single `yearfrac` from the first date
+    /// rather than a sum of consecutive fractions, which differs for non-additive conventions.
+    /// Empty for no dates.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let dates = [
+    ///     NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    /// ];
+    /// let ts = DayCountConvention::US30360.cumulative_yearfracs(&dates);
+    /// assert_eq!(ts, vec![0.0, 0.5, 1.0]);
+    /// ```
+    /// # Panics
+    /// If `dates` are not sorted in ascending order.
+    pub fn cumulative_yearfracs(&self, dates: &[NaiveDate]) -> Vec<f64> {
+        assert!(
+            dates.windows(2).all(|pair| pair[0] <= pair[1]),
+            "yearfrac: cumulative_yearfracs needs dates sorted in ascending order"
+        );
+        match dates.first() {
+            Some(first) => self.cumulative_fractions(*first, dates),
+            None => Vec::new(),
+        }
+    }
+
+    /// Difference in year fraction between conventions `a` and `b`,
+    /// i.e. the impact of switching an accrual from `b` to `a`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+    /// let delta = DayCountConvention::convention_delta(
+    ///     DayCountConvention::Act360,
+    ///     DayCountConvention::Act365,
+    ///     start,
+    ///     end,
+    /// );
+    /// assert!((delta - (90.0 / 360.0 - 90.0 / 365.0)).abs() < 1e-9);
+    /// ```
+    pub fn convention_delta(
+        a: DayCountConvention,
+        b: DayCountConvention,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> f64 {
+        a.yearfrac(start, end) - b.yearfrac(start, end)
+    }
+
+    /// Share of the period `[period_start, period_end]` elapsed at `observation`, in year fractions.
+    /// Observations outside the period give values below 0 or above 1, which are clamped
+    /// to `[0, 1]` unless `extrapolate` is set. A zero-length period is 0 before and 1 from its date.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let obs = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let rel = DayCountConvention::US30360.relative_fraction(obs, start, end, false);
+    /// assert!((rel - 0.5).abs() < 1e-9);
+    /// ```
+    pub fn relative_fraction(
+        &self,
+        observation: NaiveDate,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        extrapolate: bool,
+    ) -> f64 {
+        let period = self.yearfrac(period_start, period_end);
+        let rel = if period == 0.0 {
+            if observation < period_start {
+                0.0
+            } else {
+                1.0
+            }
+        } else {
+            self.yearfrac_signed(period_start, observation) / period
+        };
+        if extrapolate {
+            rel
+        } else {
+            rel.clamp(0.0, 1.0)
+        }
+    }
+
+    /// How far `now` is through `[period_start, period_end]` by year fraction, always within `[0, 1]`.
+    /// Meant for progress bars; see [`Self::relative_fraction`] for the unclamped value.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let late = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// assert_eq!(DayCountConvention::US30360.progress(start, late, end), 1.0);
+    /// ```
+    pub fn progress(&self, period_start: NaiveDate, now: NaiveDate, period_end: NaiveDate) -> f64 {
+        self.relative_fraction(now, period_start, period_end, false)
+    }
+
+    /// Which of the five Excel conventions gives the smallest year fraction for the dates.
+    /// Ties go to the convention that comes first in `from_int` order.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// assert_eq!(DayCountConvention::min_convention(start, end), DayCountConvention::US30360);
+    /// ```
+    pub fn min_convention(start: NaiveDate, end: NaiveDate) -> DayCountConvention {
+        Self::extreme_convention(start, end, |yf, best| yf < best)
+    }
+
+    /// Which of the five Excel conventions gives the largest year fraction for the dates.
+    /// Ties go to the convention that comes first in `from_int` order.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// assert_eq!(DayCountConvention::max_convention(start, end), DayCountConvention::Act360);
+    /// ```
+    pub fn max_convention(start: NaiveDate, end: NaiveDate) -> DayCountConvention {
+        Self::extreme_convention(start, end, |yf, best| yf > best)
+    }
+
+    fn extreme_convention(
+        start: NaiveDate,
+        end: NaiveDate,
+        better: impl Fn(f64, f64) -> bool,
+    ) -> DayCountConvention {
+        let mut best = (Self::EXCEL[0], Self::EXCEL[0].yearfrac(start, end));
+        for conv in &Self::EXCEL[1..] {
+            let yf = conv.yearfrac(start, end);
+            if better(yf, best.1) {
+                best = (*conv, yf);
+            }
+        }
+        best.0
+    }
+
+    /// Time to the start of a forward period and the length of the period:
+    /// `(yearfrac(valuation, forward_start), yearfrac(forward_start, forward_end))`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let valuation = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let forward_start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let forward_end = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let (to_start, length) =
+    ///     DayCountConvention::US30360.forward_yearfrac(valuation, forward_start, forward_end);
+    /// assert_eq!((to_start, length), (1.0, 2.0));
+    /// ```
+    /// # Panics
+    /// Unless `valuation <= forward_start <= forward_end`.
+    pub fn forward_yearfrac(
+        &self,
+        valuation: NaiveDate,
+        forward_start: NaiveDate,
+        forward_end: NaiveDate,
+    ) -> (f64, f64) {
+        assert!(
+            valuation <= forward_start && forward_start <= forward_end,
+            "yearfrac: forward_yearfrac needs valuation <= forward_start <= forward_end"
+        );
+        (
+            self.yearfrac(valuation, forward_start),
+            self.yearfrac(forward_start, forward_end),
+        )
+    }
+
+    /// Square-root-of-time factor annualising a volatility measured over one sampling
+    /// interval, i.e. `sqrt(1 / yearfrac(sample_start, sample_end))`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 2).unwrap();
+    /// let factor = DayCountConvention::Act365.annualization_sqrt_factor(start, end);
+    /// assert!((factor - 365f64.sqrt()).abs() < 1e-9);
+    /// ```
+    /// # Panics
+    /// If the sampling interval has a zero year fraction.
+    pub fn annualization_sqrt_factor(&self, sample_start: NaiveDate, sample_end: NaiveDate) -> f64 {
+        let yf = self.yearfrac(sample_start, sample_end);
+        assert!(
+            yf > 0.0,
+            "yearfrac: sampling interval has zero year fraction"
+        );
+        float::sqrt(1.0 / yf)
+    }
+
+    /// Convention name alongside the raw calendar days from `start` to `end`,
+    /// for labelling exports.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+    /// let (label, days) = DayCountConvention::Act360.labeled_days(start, end);
+    /// assert_eq!((label.as_str(), days), ("act360", 59));
+    /// ```
+    pub fn labeled_days(&self, start: NaiveDate, end: NaiveDate) -> (String, i64) {
+        (self.to_string(), (end - start).num_days())
+    }
+
+    /// Approximates the integral of `f` over `[start, end)` in year-fraction units by evaluating
+    /// `f` once per day and weighting each day by the period's year fraction per day:
+    /// one day over the basis for actual-day conventions, an equal share of the
+    /// 30/360 fraction otherwise. Bus252 only integrates over weekdays. Integrating a constant 1 returns `yearfrac(start, end)`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let area = DayCountConvention::Act365.integrate(start, end, |_| 2.0);
+    /// assert!((area - 2.0).abs() < 1e-9);
+    /// ```
+    pub fn integrate<F: Fn(NaiveDate) -> f64>(
+        &self,
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+        f: F,
+    ) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let days = (end - start).num_days();
+        if days == 0 {
+            return 0.0;
+        }
+        let weight = match self.family() {
+            ConventionFamily::Actual | ConventionFamily::Business => 1.0 / self.basis(start, end),
+            ConventionFamily::Thirty360 => self.yearfrac(start, end) / days as f64,
+        };
+        let business_only = self.family() == ConventionFamily::Business;
+        start
+            .iter_days()
+            .take_while(|day| *day < end)
+            .filter(|day| !(business_only && is_weekend(*day)))
+            .map(|day| f(day) * weight)
+            .sum()
+    }
+
+    /// Year fraction of the dates under every convention, in [`DayCountConvention::all`] order,
+    /// for reconciling against other systems side by side.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let report = DayCountConvention::compare_all(start, end);
+    /// assert_eq!(report[2], (DayCountConvention::Act360, 181.0 / 360.0));
+    /// ```
+    pub fn compare_all(start: NaiveDate, end: NaiveDate) -> Vec<(DayCountConvention, f64)> {
+        Self::all()
+            .iter()
+            .map(|conv| (*conv, conv.yearfrac(start, end)))
+            .collect()
+    }
+
+    /// Whether the choice of convention matters for the dates, i.e. the five Excel
+    /// conventions do not all agree within `1e-9`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// assert!(DayCountConvention::is_convention_sensitive(start, end));
+    /// assert!(!DayCountConvention::is_convention_sensitive(start, start));
+    /// ```
+    pub fn is_convention_sensitive(start: NaiveDate, end: NaiveDate) -> bool {
+        let first = Self::EXCEL[0].yearfrac(start, end);
+        Self::EXCEL[1..]
+            .iter()
+            .any(|conv| (conv.yearfrac(start, end) - first).abs() > 1e-9)
+    }
+
+    /// Splits the period at calendar quarter boundaries (Jan-Mar, Apr-Jun, ...) and returns
+    /// each `((year, quarter), yearfrac)` touched, quarters numbered 1 to 4.
+    /// The parts sum to `yearfrac(start, end)` for additive conventions such as Act/360.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 5, 1).unwrap();
+    /// let parts = DayCountConvention::US30360.quarterly_fractions(start, end);
+    /// assert_eq!(parts, vec![((2021, 1), 60.0 / 360.0), ((2021, 2), 30.0 / 360.0)]);
+    /// ```
+    pub fn quarterly_fractions(
+        &self,
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+    ) -> Vec<((i32, u32), f64)> {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let mut parts = Vec::new();
+        let mut from = start;
+        while from < end {
+            let quarter = (from.month() - 1) / 3 + 1;
+            let next_quarter = if quarter == 4 {
+                NaiveDate::from_ymd_opt(from.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(from.year(), quarter * 3 + 1, 1)
+            };
+            let to = next_quarter.map_or(end, |next| next.min(end));
+            parts.push(((from.year(), quarter), self.yearfrac(from, to)));
+            from = to;
+        }
+        parts
+    }
+
+    /// Years from `valuation` to `maturity`, capped at `max_years` (e.g. a 30y risk bucket).
+    /// Matured instruments (`maturity <= valuation`) get 0.0.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let valuation = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let maturity = NaiveDate::from_ymd_opt(2071, 1, 1).unwrap();
+    /// let t = DayCountConvention::US30360.time_to_maturity(valuation, maturity, 30.0);
+    /// assert_eq!(t, 30.0);
+    /// ```
+    /// # Panics
+    /// If `max_years` is negative or NaN.
+    pub fn time_to_maturity(
+        &self,
+        valuation: NaiveDate,
+        maturity: NaiveDate,
+        max_years: f64,
+    ) -> f64 {
+        assert!(
+            max_years >= 0.0,
+            "yearfrac: time to maturity needs max_years >= 0, got {max_years}"
+        );
+        if maturity <= valuation {
+            return 0.0;
+        }
+        self.yearfrac(valuation, maturity).min(max_years)
+    }
+
+    /// Daily time grid: every date from `start` to `end` inclusive with its `yearfrac(anchor, date)`,
+    /// computed once for repeated lookups. Empty if `start > end`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let anchor = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 1, 3).unwrap();
+    /// let grid = DayCountConvention::Act360.build_grid(anchor, anchor, end);
+    /// assert_eq!(grid.len(), 3);
+    /// assert_eq!(grid[2], (end, 2.0 / 360.0));
+    /// ```
+    pub fn build_grid(
+        &self,
+        anchor: NaiveDate,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<(NaiveDate, f64)> {
+        start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .map(|date| (date, self.yearfrac(anchor, date)))
+            .collect()
+    }
+
+    /// Sum of `yearfrac` over each `(start, end)` interval, e.g. the active windows of an instrument.
+    /// With `merge` set, intervals are first put in order (start before end), sorted and
+    /// overlapping or touching ones are joined, so days covered twice count once.
+    /// Without it every interval counts as given.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let d = |m| NaiveDate::from_ymd_opt(2021, m, 1).unwrap();
+    /// let intervals = [(d(1), d(4)), (d(3), d(7))];
+    /// let conv = DayCountConvention::US30360;
+    /// assert!((conv.yearfrac_intervals(&intervals, false) - 210.0 / 360.0).abs() < 1e-12);
+    /// assert_eq!(conv.yearfrac_intervals(&intervals, true), 0.5);
+    /// ```
+    pub fn yearfrac_intervals(&self, intervals: &[(NaiveDate, NaiveDate)], merge: bool) -> f64 {
+        if !merge {
+            return intervals
+                .iter()
+                .map(|(start, end)| self.yearfrac(*start, *end))
+                .sum();
+        }
+        let mut ordered: Vec<(NaiveDate, NaiveDate)> = intervals
+            .iter()
+            .map(|(start, end)| (*start.min(end), *start.max(end)))
+            .collect();
+        ordered.sort();
+        let mut merged: Vec<(NaiveDate, NaiveDate)> = Vec::with_capacity(ordered.len());
+        for (start, end) in ordered {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+            .iter()
+            .map(|(start, end)| self.yearfrac(*start, *end))
+            .sum()
+    }
+
+    /// Sum of [`Self::yearfrac_signed`] over `(start, end)` pairs, so a forward period and the
+    /// same period backwards net out, e.g. for net exposure duration.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let a = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let b = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let net = DayCountConvention::US30360.net_signed_fraction(&[(a, b), (b, a), (a, b)]);
+    /// assert_eq!(net, 0.5);
+    /// ```
+    pub fn net_signed_fraction(&self, pairs: &[(NaiveDate, NaiveDate)]) -> f64 {
+        pairs
+            .iter()
+            .map(|(start, end)| self.yearfrac_signed(*start, *end))
+            .sum()
+    }
+
+    /// Whether `yearfrac(a, b) + yearfrac(b, c) == yearfrac(a, c)` holds for all `a <= b <= c`
+    /// (up to floating point rounding), i.e. periods can be split and summed back.
+    ///
+    /// True for Act/360, Act/365, ISDA Act/Act, European 30/360 and BUS/252.
+    /// False for Excel's Act/Act, whose basis depends on the whole period, and for US 30/360,
+    /// whose end of month rules look at the start date: Jan 15 - Jan 31 - Feb 15 gives
+    /// 16 + 15 days, not 30.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert!(DayCountConvention::Act360.is_additive());
+    /// assert!(!DayCountConvention::ActAct.is_additive());
+    /// ```
+    pub fn is_additive(&self) -> bool {
+        match self {
+            DayCountConvention::Act360
+            | DayCountConvention::Act365
+            | DayCountConvention::ActActISDA
+            | DayCountConvention::EU30360
+            | DayCountConvention::Bus252
+            | DayCountConvention::Act365_25 => true,
+            DayCountConvention::ActAct | DayCountConvention::US30360 => false,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{DayCountConvention, DayCountConventionError};
+
+/// Source of holidays for business day calculations.
+/// Weekends (Saturday and Sunday) are handled separately and need not be listed.
+pub trait HolidayCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+}
+
+/// Calendar without holidays, only weekends are non-business days.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Default)]
+pub struct WeekendsOnly;
+
+impl HolidayCalendar for WeekendsOnly {
+    fn is_holiday(&self, _date: NaiveDate) -> bool {
+        false
+    }
+}
+
+impl HolidayCalendar for [NaiveDate] {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl HolidayCalendar for Vec<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl HolidayCalendar for BTreeSet<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+#[cfg(feature = "std")]
+impl HolidayCalendar for HashSet<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+/// Whether `date` is a business day: neither a Saturday, a Sunday, nor one of `holidays`.
+/// # Examples
+/// ```rust
+/// use yearfrac::is_business_day;
+/// use chrono::NaiveDate;
+/// let monday = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+/// let saturday = NaiveDate::from_ymd_opt(2021, 3, 6).unwrap();
+/// assert!(is_business_day(monday, &[]));
+/// assert!(!is_business_day(saturday, &[]));
+/// assert!(!is_business_day(monday, &[monday]));
+/// ```
+pub fn is_business_day(date: NaiveDate, holidays: &[NaiveDate]) -> bool {
+    !is_weekend(date) && !holidays.is_holiday(date)
+}
+
+/// How a date falling on a weekend or holiday is moved to a business day.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum BusinessDayConvention {
+    /// The next business day
+    Following,
+    /// The next business day, unless that is in the next month: then the previous business day
+    ModifiedFollowing,
+    /// The previous business day
+    Preceding,
+}
+
+/// Moves `date` to a business day (not a weekend nor one of `holidays`) following `conv`.
+/// Business days are returned unchanged.
+/// # Examples
+/// ```rust
+/// use yearfrac::{adjust, BusinessDayConvention};
+/// use chrono::NaiveDate;
+/// let saturday = NaiveDate::from_ymd_opt(2021, 7, 31).unwrap();
+/// let monday = NaiveDate::from_ymd_opt(2021, 8, 2).unwrap();
+/// let friday = NaiveDate::from_ymd_opt(2021, 7, 30).unwrap();
+/// assert_eq!(adjust(saturday, BusinessDayConvention::Following, &[]), monday);
+/// assert_eq!(adjust(saturday, BusinessDayConvention::ModifiedFollowing, &[]), friday);
+/// ```
+/// # Panics
+/// If there is no business day between `date` and the end of chrono's supported range.
+pub fn adjust(date: NaiveDate, conv: BusinessDayConvention, holidays: &[NaiveDate]) -> NaiveDate {
+    match conv {
+        BusinessDayConvention::Following => roll(date, holidays, NaiveDate::succ_opt),
+        BusinessDayConvention::Preceding => roll(date, holidays, NaiveDate::pred_opt),
+        BusinessDayConvention::ModifiedFollowing => {
+            let following = roll(date, holidays, NaiveDate::succ_opt);
+            if following.month() == date.month() {
+                following
+            } else {
+                roll(date, holidays, NaiveDate::pred_opt)
+            }
+        }
+    }
+}
+
+/// Steps from `date` with `step` until a business day
+fn roll(
+    mut date: NaiveDate,
+    holidays: &[NaiveDate],
+    step: fn(&NaiveDate) -> Option<NaiveDate>,
+) -> NaiveDate {
+    while !is_business_day(date, holidays) {
+        date = step(&date).expect("yearfrac: no business day within chrono's date range");
+    }
+    date
+}
+
+pub(crate) fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Weekdays in `[start, end)`, assuming `start <= end`: five per whole week,
+/// plus the weekdays among the leftover days
+fn weekdays(start: NaiveDate, end: NaiveDate) -> i64 {
+    let days = (end - start).num_days();
+    let first = start.weekday().num_days_from_monday() as i64;
+    let leftover = (0..days % 7).filter(|i| (first + i) % 7 < 5).count() as i64;
+    days / 7 * 5 + leftover
+}
+
+/// Business days in `[start, end)`, assuming `start <= end`.
+/// Holidays are counted once each, and only when they fall on a weekday inside the range.
+pub(crate) fn business_days(start: NaiveDate, end: NaiveDate, holidays: &[NaiveDate]) -> u32 {
+    let holidays: BTreeSet<NaiveDate> = holidays
+        .iter()
+        .copied()
+        .filter(|d| start <= *d && *d < end && !is_weekend(*d))
+        .collect();
+    (weekdays(start, end) - holidays.len() as i64) as u32
+}
+
+/// Brazilian BUS/252 year fraction: business days in `[start, end)` over 252,
+/// skipping weekends and the given `holidays` (e.g. the ANBIMA calendar).
+/// With no holidays this is [`DayCountConvention::Bus252`]'s `yearfrac`.
+/// # Examples
+/// ```rust
+/// use yearfrac::yearfrac_bus252;
+/// use chrono::NaiveDate;
+/// let start = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(); // Monday
+/// let end = NaiveDate::from_ymd_opt(2021, 3, 15).unwrap();
+/// let holiday = NaiveDate::from_ymd_opt(2021, 3, 3).unwrap();
+/// assert_eq!(yearfrac_bus252(start, end, &[]), 10.0 / 252.0);
+/// assert_eq!(yearfrac_bus252(start, end, &[holiday]), 9.0 / 252.0);
+/// ```
+pub fn yearfrac_bus252(mut start: NaiveDate, mut end: NaiveDate, holidays: &[NaiveDate]) -> f64 {
+    if start > end {
+        (start, end) = (end, start)
+    }
+    business_days(start, end, holidays) as f64 / 252.0
+}
+
+impl DayCountConvention {
+    /// Splits the actual days of `[start, end)` into business days, weekend days and holidays,
+    /// each over the convention's basis: `(business, weekend, holiday)`.
+    /// Holidays falling on a weekend count as weekend days.
+    /// The three parts sum to the actual days over the basis, which is `yearfrac` for the
+    /// actual-day conventions.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, WeekendsOnly};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(); // Monday
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 15).unwrap();
+    /// let (business, weekend, holiday) =
+    ///     DayCountConvention::Act360.fraction_by_weekday_class(start, end, &WeekendsOnly);
+    /// assert_eq!((business, weekend, holiday), (10.0 / 360.0, 4.0 / 360.0, 0.0));
+    /// ```
+    pub fn fraction_by_weekday_class(
+        &self,
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+        calendar: &dyn HolidayCalendar,
+    ) -> (f64, f64, f64) {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let (mut business, mut weekend, mut holiday) = (0u32, 0u32, 0u32);
+        for date in start.iter_days().take_while(|d| *d < end) {
+            if is_weekend(date) {
+                weekend += 1;
+            } else if calendar.is_holiday(date) {
+                holiday += 1;
+            } else {
+                business += 1;
+            }
+        }
+        let basis = self.basis(start, end);
+        (
+            business as f64 / basis,
+            weekend as f64 / basis,
+            holiday as f64 / basis,
+        )
+    }
+
+    /// Checks that both ends of an accrual period are business days under `calendar`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, WeekendsOnly};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(); // Monday
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 6).unwrap(); // Saturday
+    /// assert!(DayCountConvention::require_business_days(start, start, &WeekendsOnly).is_ok());
+    /// assert!(DayCountConvention::require_business_days(start, end, &WeekendsOnly).is_err());
+    /// ```
+    /// # Errors
+    /// `NonBusinessDay` with the first offending date.
+    pub fn require_business_days(
+        start: NaiveDate,
+        end: NaiveDate,
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<(), DayCountConventionError> {
+        for date in [start, end] {
+            if is_weekend(date) || calendar.is_holiday(date) {
+                return Err(DayCountConventionError::NonBusinessDay { date });
+            }
+        }
+        Ok(())
+    }
+
+    /// Year fraction between `start` and `end` after moving each of them to a business day
+    /// with [`adjust`], as done for accrual periods whose nominal dates fall on a holiday.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{BusinessDayConvention, DayCountConvention};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(); // Friday, a holiday
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 3).unwrap(); // Saturday
+    /// let holidays = [start];
+    /// let yf = DayCountConvention::Act360.yearfrac_adjusted(
+    ///     start,
+    ///     end,
+    ///     BusinessDayConvention::Following,
+    ///     &holidays,
+    /// );
+    /// // Jan 4 to Jul 5
+    /// assert_eq!(yf, 182.0 / 360.0);
+    /// ```
+    /// # Panics
+    /// If either date cannot be adjusted, see [`adjust`].
+    pub fn yearfrac_adjusted(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        bdc: BusinessDayConvention,
+        holidays: &[NaiveDate],
+    ) -> f64 {
+        self.yearfrac(adjust(start, bdc, holidays), adjust(end, bdc, holidays))
+    }
+}
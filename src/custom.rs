@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+
+use crate::{DayCountConvention, DayCountConventionError};
+
+/// How a [`CustomConvention`] counts the days between two dates.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Numerator {
+    /// Actual calendar days
+    Actual,
+    /// 30 day months with the US (NASD) rules of [`DayCountConvention::US30360`]
+    Us30360,
+    /// 30 day months with the European rules of [`DayCountConvention::EU30360`]
+    Eu30360,
+}
+
+/// A day count with a user chosen numerator and basis, for conventions
+/// without their own variant such as Act/364 or 30/365.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CustomConvention {
+    numerator: Numerator,
+    basis: f64,
+}
+
+impl CustomConvention {
+    /// Counts days with `numerator` and divides them by `basis`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{CustomConvention, Numerator};
+    /// use chrono::NaiveDate;
+    /// let act364 = CustomConvention::new(Numerator::Actual, 364.0).unwrap();
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+    /// assert_eq!(act364.yearfrac(start, end), 90.0 / 364.0);
+    /// ```
+    /// # Errors
+    /// `InvalidInput` if `basis` is NaN, infinite or not positive.
+    pub fn new(numerator: Numerator, basis: f64) -> Result<Self, DayCountConventionError> {
+        if !(basis.is_finite() && basis > 0.0) {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "custom convention basis has to be finite and positive",
+            });
+        }
+        Ok(CustomConvention { numerator, basis })
+    }
+
+    /// Calculates year fruction, swapping the dates if `start > end` like [`DayCountConvention::yearfrac`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{CustomConvention, Numerator};
+    /// use chrono::NaiveDate;
+    /// let thirty365 = CustomConvention::new(Numerator::Us30360, 365.0).unwrap();
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// assert_eq!(thirty365.yearfrac(start, end), 60.0 / 365.0);
+    /// ```
+    pub fn yearfrac(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let days = match self.numerator {
+            Numerator::Actual => DayCountConvention::Act365.diff_dts(start, end),
+            Numerator::Us30360 => DayCountConvention::US30360.diff_dts(start, end),
+            Numerator::Eu30360 => DayCountConvention::EU30360.diff_dts(start, end),
+        };
+        days / self.basis
+    }
+}
@@ -0,0 +1,250 @@
+use chrono::NaiveDate;
+
+use alloc::vec::Vec;
+
+use crate::{check_finite, float, DayCountConvention, DayCountConventionError};
+
+/// How interest compounds when discounting over a year fraction `t` at rate `r`.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Compounding {
+    /// 1 / (1 + r t)
+    Simple,
+    /// (1 + r / n)^(-n t) with `n` compounding periods per year, e.g. `Periodic(1)` for annual
+    Periodic(u32),
+    /// exp(-r t)
+    Continuous,
+}
+
+impl Compounding {
+    fn discount_factor(&self, rate: f64, t: f64) -> Result<f64, DayCountConventionError> {
+        check_finite("rate", rate)?;
+        match self {
+            Compounding::Simple => Ok(1.0 / (1.0 + rate * t)),
+            Compounding::Periodic(0) => Err(DayCountConventionError::InvalidInput {
+                reason: "periodic compounding needs at least one period per year",
+            }),
+            Compounding::Periodic(n) => {
+                let n = *n as f64;
+                Ok(float::powf(1.0 + rate / n, -n * t))
+            }
+            Compounding::Continuous => Ok(float::exp(-rate * t)),
+        }
+    }
+}
+
+/// Maximum number of Newton steps taken by [`DayCountConvention::yield_to_maturity`]
+const YTM_MAX_ITERATIONS: usize = 100;
+/// The yield solver stops once a Newton step moves the rate by less than this
+const YTM_TOLERANCE: f64 = 1e-12;
+
+impl DayCountConvention {
+    /// Discount factor from `end` back to `start` at `rate`, using this convention's year fraction.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{Compounding, DayCountConvention};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let df = DayCountConvention::US30360
+    ///     .discount_factor(start, end, 0.05, Compounding::Periodic(1))
+    ///     .unwrap();
+    /// assert!((df - 1.0 / 1.05).abs() < 1e-12);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `rate` is NaN or infinite, `InvalidInput` on `Compounding::Periodic(0)`.
+    pub fn discount_factor(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        rate: f64,
+        compounding: Compounding,
+    ) -> Result<f64, DayCountConventionError> {
+        compounding.discount_factor(rate, self.yearfrac(start, end))
+    }
+
+    /// Simple interest discount factor `1 / (1 + rate * yearfrac(start, end))`,
+    /// shorthand for [`DayCountConvention::discount_factor`] with [`Compounding::Simple`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let df = DayCountConvention::US30360.simple_discount_factor(start, end, 0.04).unwrap();
+    /// assert!((df - 1.0 / 1.02).abs() < 1e-12);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `rate` is NaN or infinite.
+    pub fn simple_discount_factor(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        rate: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        self.discount_factor(start, end, rate, Compounding::Simple)
+    }
+
+    /// Continuously compounded discount factor `exp(-rate * yearfrac(start, end))`,
+    /// shorthand for [`DayCountConvention::discount_factor`] with [`Compounding::Continuous`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let df = DayCountConvention::US30360.continuous_discount_factor(start, end, 0.05).unwrap();
+    /// assert!((df - (-0.1f64).exp()).abs() < 1e-12);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `rate` is NaN or infinite.
+    pub fn continuous_discount_factor(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        rate: f64,
+    ) -> Result<f64, DayCountConventionError> {
+        self.discount_factor(start, end, rate, Compounding::Continuous)
+    }
+
+    /// Price of a zero-coupon bond paying `face` at `maturity`, discounted to `settle` at `yield_rate`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{Compounding, DayCountConvention};
+    /// use chrono::NaiveDate;
+    /// let settle = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let maturity = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    /// let price = DayCountConvention::US30360.zero_coupon_price(
+    ///     settle,
+    ///     maturity,
+    ///     100.0,
+    ///     0.05,
+    ///     Compounding::Continuous,
+    /// )
+    /// .unwrap();
+    /// assert!((price - 100.0 * (-0.25f64).exp()).abs() < 1e-9);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` if `face` or `yield_rate` is NaN or infinite, `InvalidInput` on `Compounding::Periodic(0)`.
+    pub fn zero_coupon_price(
+        &self,
+        settle: NaiveDate,
+        maturity: NaiveDate,
+        face: f64,
+        yield_rate: f64,
+        compounding: Compounding,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("face", face)?;
+        check_finite("yield_rate", yield_rate)?;
+        Ok(face * self.discount_factor(settle, maturity, yield_rate, compounding)?)
+    }
+
+    /// Macaulay duration in years: the present-value weighted average time of `cashflows`,
+    /// `sum(t_i * PV_i) / sum(PV_i)` with `t_i = yearfrac(settle, date_i)`,
+    /// discounted at `yield_rate`. Cashflows on or before `settle` are ignored.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{Compounding, DayCountConvention};
+    /// use chrono::NaiveDate;
+    /// let settle = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let cashflows = [(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 100.0)];
+    /// let duration = DayCountConvention::US30360
+    ///     .macaulay_duration(settle, &cashflows, 0.05, Compounding::Periodic(1))
+    ///     .unwrap();
+    /// assert!((duration - 3.0).abs() < 1e-12);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` on NaN or infinite amounts or yield, `InvalidInput` on
+    /// `Compounding::Periodic(0)` or if the cashflows have no present value.
+    pub fn macaulay_duration(
+        &self,
+        settle: NaiveDate,
+        cashflows: &[(NaiveDate, f64)],
+        yield_rate: f64,
+        compounding: Compounding,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("yield_rate", yield_rate)?;
+        let (mut weighted, mut total) = (0.0, 0.0);
+        for (date, amount) in cashflows.iter().filter(|(date, _)| *date > settle) {
+            check_finite("cashflow", *amount)?;
+            let pv = amount * self.discount_factor(settle, *date, yield_rate, compounding)?;
+            weighted += self.yearfrac(settle, *date) * pv;
+            total += pv;
+        }
+        if total == 0.0 {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "cashflows have zero present value",
+            });
+        }
+        Ok(weighted / total)
+    }
+
+    /// Yield to maturity: the rate at which `cashflows` discounted to `settle` are worth `price`.
+    /// Cashflows on or before `settle` are ignored, as in [`Self::macaulay_duration`].
+    ///
+    /// Solved with Newton's method from an initial guess of 5%, using a central difference
+    /// for the derivative. The solver converges once a step moves the rate by less than
+    /// `1e-12` and gives up after 100 steps.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{Compounding, DayCountConvention};
+    /// use chrono::NaiveDate;
+    /// let d = |y| NaiveDate::from_ymd_opt(y, 1, 1).unwrap();
+    /// let cashflows = [(d(2022), 6.0), (d(2023), 6.0), (d(2024), 106.0)];
+    /// let ytm = DayCountConvention::US30360
+    ///     .yield_to_maturity(d(2021), 100.0, &cashflows, Compounding::Periodic(1))
+    ///     .unwrap();
+    /// assert!((ytm - 0.06).abs() < 1e-10);
+    /// ```
+    /// # Errors
+    /// `NonFiniteInput` on NaN or infinite price or amounts, `InvalidInput` on
+    /// `Compounding::Periodic(0)` or if no cashflow falls after `settle`,
+    /// `NoConvergence` if the iteration limit is reached or a step leaves the domain of the
+    /// discount function (e.g. a price no yield can reach).
+    pub fn yield_to_maturity(
+        &self,
+        settle: NaiveDate,
+        price: f64,
+        cashflows: &[(NaiveDate, f64)],
+        compounding: Compounding,
+    ) -> Result<f64, DayCountConventionError> {
+        check_finite("price", price)?;
+        let future: Vec<(f64, f64)> = cashflows
+            .iter()
+            .filter(|(date, _)| *date > settle)
+            .map(|(date, amount)| {
+                check_finite("cashflow", *amount)?;
+                Ok((self.yearfrac(settle, *date), *amount))
+            })
+            .collect::<Result<_, DayCountConventionError>>()?;
+        if future.is_empty() {
+            return Err(DayCountConventionError::InvalidInput {
+                reason: "no cashflows after settlement",
+            });
+        }
+        let present_value = |rate: f64| -> Result<f64, DayCountConventionError> {
+            future.iter().try_fold(0.0, |pv, (t, amount)| {
+                Ok(pv + amount * compounding.discount_factor(rate, *t)?)
+            })
+        };
+
+        let h = 1e-7;
+        let mut rate = 0.05;
+        for iterations in 1..=YTM_MAX_ITERATIONS {
+            let diff = present_value(rate)? - price;
+            let slope = (present_value(rate + h)? - present_value(rate - h)?) / (2.0 * h);
+            let step = diff / slope;
+            if !step.is_finite() {
+                return Err(DayCountConventionError::NoConvergence { iterations });
+            }
+            rate -= step;
+            if step.abs() < YTM_TOLERANCE {
+                return Ok(rate);
+            }
+        }
+        Err(DayCountConventionError::NoConvergence {
+            iterations: YTM_MAX_ITERATIONS,
+        })
+    }
+}
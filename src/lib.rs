@@ -34,11 +34,36 @@
 //! let yf = DayCountConvention::US30360.yearfrac_signed(end, start);
 //! assert!((yf + 42.21388888889).abs() < 1e-9);
 //! ```
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use chrono::{Datelike, NaiveDate};
-use std::str::FromStr;
+use core::fmt;
+use core::str::FromStr;
 use thiserror::Error;
 
+mod accrual;
+mod analytics;
+mod calendar;
+mod custom;
+mod discount;
+mod float;
+mod schedule;
+mod thirty360;
+
+use calendar::business_days;
+pub use calendar::{
+    adjust, is_business_day, yearfrac_bus252, BusinessDayConvention, HolidayCalendar, WeekendsOnly,
+};
+pub use custom::{CustomConvention, Numerator};
+pub use discount::Compounding;
+pub use schedule::{add_months, validate_schedule, yearfrac_icma, Frequency};
+pub use thirty360::{Thirty360, Thirty360Builder, Thirty360Preset};
+
 /// #Examples
 /// ```rust
 /// use chrono::{NaiveDate, Datelike};
@@ -48,12 +73,47 @@ use thiserror::Error;
 /// assert_eq!(is_leap_year(dt.year()) as i32, 0)
 #[allow(clippy::if_same_then_else)]
 pub fn is_leap_year(year: i32) -> bool {
-    if year % 4 > 0 {
+    // rem_euclid keeps years <= 0 on chrono's proleptic Gregorian calendar (year 0 is 1 BCE)
+    if year.rem_euclid(4) > 0 {
         false
-    } else if year % 100 > 0 {
+    } else if year.rem_euclid(100) > 0 {
         true
     } else {
-        year % 400 == 0
+        year.rem_euclid(400) == 0
+    }
+}
+
+/// Number of days in `year`: 366 for leap years, 365 otherwise.
+/// # Examples
+/// ```rust
+/// use yearfrac::days_in_year;
+/// assert_eq!(days_in_year(2020), 366);
+/// assert_eq!(days_in_year(1900), 365);
+/// ```
+pub fn days_in_year(year: i32) -> u32 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+/// Number of days in `month` (1-12) of `year`: 28 to 31.
+/// # Examples
+/// ```rust
+/// use yearfrac::days_in_month;
+/// assert_eq!(days_in_month(2, 2020), 29);
+/// assert_eq!(days_in_month(4, 2021), 30);
+/// ```
+/// # Panics
+/// If `month` is not in 1-12.
+pub fn days_in_month(month: u32, year: i32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => panic!("yearfrac: month has to be 1-12, got {month}"),
     }
 }
 
@@ -64,6 +124,8 @@ pub fn is_leap_year(year: i32) -> bool {
 ///
 /// use yearfrac::is_end_of_month;
 /// assert!(is_end_of_month(dt.day(), dt.month(), dt.year()))
+/// ```
+/// Months outside 1-12 are treated like February, see [`try_is_end_of_month`] to reject them.
 pub fn is_end_of_month(day: u32, month: u32, year: i32) -> bool {
     if [1, 3, 5, 7, 8, 10, 12].contains(&month) {
         day == 31
@@ -76,18 +138,122 @@ pub fn is_end_of_month(day: u32, month: u32, year: i32) -> bool {
     }
 }
 
-#[derive(Hash, Clone, Copy, Debug, PartialEq)]
+/// Checked [`is_end_of_month`]: `None` if `month` is not in 1-12.
+/// # Examples
+/// ```rust
+/// use yearfrac::try_is_end_of_month;
+/// assert_eq!(try_is_end_of_month(30, 4, 2021), Some(true));
+/// assert_eq!(try_is_end_of_month(28, 13, 2021), None);
+/// ```
+pub fn try_is_end_of_month(day: u32, month: u32, year: i32) -> Option<bool> {
+    (1..=12)
+        .contains(&month)
+        .then(|| day == days_in_month(month, year))
+}
+
+/// Primitive-only [`DayCountConvention::yearfrac`] for FFI and WebAssembly callers:
+/// `conv` is a [`DayCountConvention::from_int`] code and the dates are given as year, month, day.
+/// # Examples
+/// ```rust
+/// use yearfrac::yearfrac_ymd;
+/// let yf = yearfrac_ymd(0, 1978, 2, 28, 2020, 5, 17);
+/// assert!((yf - 42.21388888889).abs() < 1e-9);
+/// ```
+/// # Panics
+/// If `conv` is not a valid code or either date does not exist.
+pub fn yearfrac_ymd(conv: u8, y1: i32, m1: u32, d1: u32, y2: i32, m2: u32, d2: u32) -> f64 {
+    let conv = DayCountConvention::from_int(conv).unwrap_or_else(|e| panic!("yearfrac: {e}"));
+    let start = NaiveDate::from_ymd_opt(y1, m1, d1)
+        .unwrap_or_else(|| panic!("yearfrac: invalid start date {y1}-{m1}-{d1}"));
+    let end = NaiveDate::from_ymd_opt(y2, m2, d2)
+        .unwrap_or_else(|| panic!("yearfrac: invalid end date {y2}-{m2}-{d2}"));
+    conv.yearfrac(start, end)
+}
+
+/// With the `serde` feature a convention (de)serializes as its `from_str` token, e.g. "nasd30/360".
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum DayCountConvention {
+    /// Excel's YEARFRAC basis 0. The last day of February (Feb 28, or Feb 29 in a leap year)
+    /// counts as day 30 as a start date, and as an end date only when the start is also
+    /// the last day of February.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "nasd30/360"))]
     US30360,
+    #[cfg_attr(feature = "serde", serde(rename = "act/act"))]
     ActAct,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "act360", alias = "actual/360", alias = "act/360")
+    )]
     Act360,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "act365", alias = "actual/365 fixed", alias = "act/365f")
+    )]
     Act365,
+    /// Excel's YEARFRAC basis 4. Only the 31st is rolled to 30, so Feb 28 and Feb 29
+    /// keep their day number on either side.
+    #[cfg_attr(feature = "serde", serde(rename = "eur30/360"))]
     EU30360,
+    /// ISDA Act/Act: the days falling in each calendar year over that year's length (365 or 366),
+    /// summed. Unlike [`DayCountConvention::ActAct`] this is not part of Excel's YEARFRAC.
+    #[cfg_attr(feature = "serde", serde(rename = "act/act/isda"))]
+    ActActISDA,
+    /// Brazilian BUS/252: business days over 252. `yearfrac` only skips weekends,
+    /// see [`yearfrac_bus252`] to also skip holidays.
+    #[cfg_attr(feature = "serde", serde(rename = "bus/252"))]
+    Bus252,
+    /// Actual days over a fixed 365.25 day year, the average Julian year length.
+    #[cfg_attr(feature = "serde", serde(rename = "act/365.25"))]
+    Act365_25,
+}
+
+/// Groups conventions by how they count days.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ConventionFamily {
+    /// 30 day months, 360 day year
+    Thirty360,
+    /// Actual calendar days
+    Actual,
+    /// Business days only
+    Business,
 }
 
 impl DayCountConvention {
+    /// Excel's YEARFRAC conventions in `from_int` order
+    const EXCEL: [DayCountConvention; 5] = [
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+        DayCountConvention::EU30360,
+    ];
+
+    /// Every convention, in `from_int` order (`all()[i] == from_int(i)`).
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// for conv in DayCountConvention::all() {
+    ///     assert_eq!(DayCountConvention::from_str(&conv.to_string()).unwrap(), conv);
+    /// }
+    /// ```
+    pub const fn all() -> [DayCountConvention; 8] {
+        [
+            DayCountConvention::US30360,
+            DayCountConvention::ActAct,
+            DayCountConvention::Act360,
+            DayCountConvention::Act365,
+            DayCountConvention::EU30360,
+            DayCountConvention::ActActISDA,
+            DayCountConvention::Bus252,
+            DayCountConvention::Act365_25,
+        ]
+    }
+
     /// Generates DayCountConvention enum from an u8;
     /// Acceptable values:
     ///
@@ -101,6 +267,12 @@ impl DayCountConvention {
     ///    
     /// 4 for eur30/360
     ///
+    /// 5 for act/act/isda
+    ///
+    /// 6 for bus/252
+    ///
+    /// 7 for act/365.25
+    ///
     /// # Examples
     /// ```rust
     /// use yearfrac::DayCountConvention;
@@ -110,7 +282,7 @@ impl DayCountConvention {
     ///  ```should_panic
     /// use yearfrac::DayCountConvention;
     ///
-    /// let yf = DayCountConvention::from_int(5).unwrap();
+    /// let yf = DayCountConvention::from_int(8).unwrap();
     /// ```
     pub fn from_int(day_count_convention: u8) -> Result<Self, DayCountConventionError> {
         match day_count_convention {
@@ -119,24 +291,116 @@ impl DayCountConvention {
             2 => Ok(DayCountConvention::Act360),
             3 => Ok(DayCountConvention::Act365),
             4 => Ok(DayCountConvention::EU30360),
+            5 => Ok(DayCountConvention::ActActISDA),
+            6 => Ok(DayCountConvention::Bus252),
+            7 => Ok(DayCountConvention::Act365_25),
             other => Err(DayCountConventionError::InvalidValue {
                 val: other.to_string(),
             }),
         }
     }
+    /// Convention for the `basis` argument of Excel's `YEARFRAC(start_date, end_date, [basis])`:
+    /// 0 US (NASD) 30/360, 1 Actual/actual, 2 Actual/360, 3 Actual/365, 4 European 30/360.
+    /// Same codes as [`DayCountConvention::from_int`], but only Excel's five are accepted.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// let conv = DayCountConvention::from_excel_basis(1).unwrap();
+    /// assert_eq!(conv, DayCountConvention::ActAct);
+    /// assert!(DayCountConvention::from_excel_basis(5).is_err());
+    /// ```
+    /// # Errors
+    /// `InvalidExcelBasis` if `basis` is not 0-4, like Excel's `#NUM!`.
+    pub fn from_excel_basis(basis: u8) -> Result<Self, DayCountConventionError> {
+        if (basis as usize) < Self::EXCEL.len() {
+            Self::from_int(basis)
+        } else {
+            Err(DayCountConventionError::InvalidExcelBasis { val: basis })
+        }
+    }
+    /// Converts a column of integer codes (see [`DayCountConvention::from_int`]) at once.
+    /// Fails on the first invalid code, reporting its position.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// let convs = DayCountConvention::from_ints(&[0, 3]).unwrap();
+    /// assert_eq!(convs, vec![DayCountConvention::US30360, DayCountConvention::Act365]);
+    /// assert!(DayCountConvention::from_ints(&[0, 9]).is_err());
+    /// ```
+    pub fn from_ints(codes: &[u8]) -> Result<Vec<Self>, DayCountConventionError> {
+        codes
+            .iter()
+            .enumerate()
+            .map(|(index, code)| {
+                Self::from_int(*code).map_err(|_| DayCountConventionError::InvalidValueAt {
+                    index,
+                    val: code.to_string(),
+                })
+            })
+            .collect()
+    }
+    /// Compact wire representation: every convention serializes to a single byte,
+    /// the same code accepted by [`DayCountConvention::from_int`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(DayCountConvention::Act365.to_byte(), 3);
+    /// ```
+    pub fn to_byte(&self) -> u8 {
+        self.to_int()
+    }
+    /// Stable integer code of the convention, the inverse of [`DayCountConvention::from_int`].
+    /// Fixed per convention, so it does not change if variants are reordered.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// const CODE: u8 = DayCountConvention::EU30360.to_int();
+    /// assert_eq!(CODE, 4);
+    /// assert_eq!(DayCountConvention::from_int(CODE).unwrap(), DayCountConvention::EU30360);
+    /// ```
+    pub const fn to_int(&self) -> u8 {
+        match self {
+            DayCountConvention::US30360 => 0,
+            DayCountConvention::ActAct => 1,
+            DayCountConvention::Act360 => 2,
+            DayCountConvention::Act365 => 3,
+            DayCountConvention::EU30360 => 4,
+            DayCountConvention::ActActISDA => 5,
+            DayCountConvention::Bus252 => 6,
+            DayCountConvention::Act365_25 => 7,
+        }
+    }
+    /// Reads a convention back from the byte written by [`DayCountConvention::to_byte`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// let conv = DayCountConvention::EU30360;
+    /// assert_eq!(DayCountConvention::from_byte(conv.to_byte()).unwrap(), conv);
+    /// assert!(DayCountConvention::from_byte(8).is_err());
+    /// ```
+    pub fn from_byte(b: u8) -> Result<Self, DayCountConventionError> {
+        Self::from_int(b)
+    }
     /// Generates DayCountConvention enum from a &str;
+    /// Case-insensitive and surrounding whitespace is ignored.
     /// Acceptable values:
     ///
     /// nasd30/360
     ///
     /// act/act
     ///
-    /// act360  
+    /// act360 (also actual/360 or act/360)
     ///   
-    /// act365
+    /// act365 (also ISDA's actual/365 fixed or act/365f)
     ///    
     /// eur30/360
     ///
+    /// act/act/isda
+    ///
+    /// bus/252
+    ///
+    /// act/365.25
+    ///
     /// /// # Examples
     /// ```rust
     /// use yearfrac::DayCountConvention;
@@ -155,7 +419,84 @@ impl DayCountConvention {
         <Self as FromStr>::from_str(day_count_convention)
     }
 
+    /// Every spelling [`DayCountConvention::from_str`] accepts for this convention,
+    /// the canonical token (as written by `Display`) first.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(
+    ///     DayCountConvention::Act360.aliases(),
+    ///     &["act360", "actual/360", "act/360"]
+    /// );
+    /// ```
+    pub const fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            DayCountConvention::US30360 => &["nasd30/360"],
+            DayCountConvention::ActAct => &["act/act"],
+            DayCountConvention::Act360 => &["act360", "actual/360", "act/360"],
+            DayCountConvention::Act365 => &["act365", "actual/365 fixed", "act/365f"],
+            DayCountConvention::EU30360 => &["eur30/360"],
+            DayCountConvention::ActActISDA => &["act/act/isda"],
+            DayCountConvention::Bus252 => &["bus/252"],
+            DayCountConvention::Act365_25 => &["act/365.25"],
+        }
+    }
+
+    /// Canonical token of every convention in [`DayCountConvention::all`] order, e.g. for
+    /// a CLI's value parser and help text. Other spellings are listed by [`DayCountConvention::aliases`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(DayCountConvention::possible_values()[0], "nasd30/360");
+    /// ```
+    pub fn possible_values() -> &'static [&'static str] {
+        &Self::CANONICAL_TOKENS
+    }
+
+    /// `aliases()[0]` of every convention, so the token list has a single source
+    const CANONICAL_TOKENS: [&'static str; DayCountConvention::all().len()] = {
+        let all = Self::all();
+        let mut tokens = [""; DayCountConvention::all().len()];
+        let mut i = 0;
+        while i < all.len() {
+            tokens[i] = all[i].aliases()[0];
+            i += 1;
+        }
+        tokens
+    };
+
+    /// The canonical [`DayCountConvention::from_str`] token, as written by `Display`,
+    /// without allocating.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(DayCountConvention::US30360.short_name(), "nasd30/360");
+    /// ```
+    pub const fn short_name(&self) -> &'static str {
+        self.aliases()[0]
+    }
+
+    /// Human readable name of the convention, e.g. for tooltips.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(DayCountConvention::Act360.description(), "Actual/360");
+    /// ```
+    pub fn description(&self) -> &'static str {
+        match self {
+            DayCountConvention::US30360 => "US (NASD) 30/360 Bond Basis",
+            DayCountConvention::ActAct => "Actual/Actual (Excel)",
+            DayCountConvention::Act360 => "Actual/360",
+            DayCountConvention::Act365 => "Actual/365 Fixed",
+            DayCountConvention::EU30360 => "European 30/360 Eurobond Basis",
+            DayCountConvention::ActActISDA => "Actual/Actual ISDA",
+            DayCountConvention::Bus252 => "Business/252 (Brazil)",
+            DayCountConvention::Act365_25 => "Actual/365.25",
+        }
+    }
+
     /// Calculates year fruction.
+    /// If `start > end` the dates are swapped; see [`DayCountConvention::try_yearfrac`] to reject them.
     /// # Examples
     /// ```rust
     /// use yearfrac::DayCountConvention;
@@ -166,19 +507,104 @@ impl DayCountConvention {
     ///                .yearfrac(start, end);
     ///assert!((yf - 42.21388888889).abs() < 1e-9);
     /// ```
-    #[allow(clippy::comparison_chain)]
     pub fn yearfrac(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
-        if start == end {
-            return 0.0; //edge case
-        } else if start > end {
+        // Equal dates need no fast path: every day count is 0 and every basis is
+        // nonzero (ActAct falls in its same-year branch), so the result is exactly 0.0
+        if start > end {
             (start, end) = (end, start)
         }
+        if let DayCountConvention::ActActISDA = self {
+            return self.act_act_isda(start, end);
+        }
         let numerator = self.diff_dts(start, end);
         let denom = self.basis(start, end);
         numerator / denom
     }
+    /// Same as [`DayCountConvention::yearfrac`], plus the day adjustments applied along the way,
+    /// in the order they fired. Useful to audit a 30/360 result against another system.
+    ///
+    /// Possible entries: "start_31_to_30", "start_feb_eom_to_30", "end_31_to_30" and
+    /// "end_feb_eom_to_30"; the February rules only exist for US30360.
+    /// The trace is always empty for the actual conventions. Equal dates go through the
+    /// same rules, so a month-end date traced against itself still lists its adjustments.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 30).unwrap();
+    /// let (yf, trace) = DayCountConvention::US30360.yearfrac_traced(start, end);
+    /// assert_eq!(yf, 30.0 / 360.0);
+    /// assert_eq!(trace, vec!["start_feb_eom_to_30"]);
+    /// ```
+    pub fn yearfrac_traced(
+        &self,
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+    ) -> (f64, Vec<&'static str>) {
+        let mut trace = Vec::new();
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let record = |rule| trace.push(rule);
+        let numerator = match self {
+            DayCountConvention::US30360 => self.nasd360_with(start, end, 0, true, true, record),
+            DayCountConvention::EU30360 => self.euro360_with(start, end, record),
+            _ => return (self.yearfrac(start, end), trace),
+        };
+        (numerator / self.basis(start, end), trace)
+    }
+    /// Strict version of [`DayCountConvention::yearfrac`] for auditing:
+    /// reversed dates are an error instead of being swapped.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// let conv = DayCountConvention::US30360;
+    /// assert_eq!(conv.try_yearfrac(start, end).unwrap(), 0.5);
+    /// assert!(conv.try_yearfrac(end, start).is_err());
+    /// ```
+    /// # Errors
+    /// `StartAfterEnd` if `start > end`.
+    pub fn try_yearfrac(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<f64, DayCountConventionError> {
+        if start > end {
+            return Err(DayCountConventionError::StartAfterEnd { start, end });
+        }
+        Ok(self.yearfrac(start, end))
+    }
+    /// [`DayCountConvention::yearfrac`] as a `Result`, the entry point for error-returning
+    /// pipelines, like Excel's YEARFRAC returning `#NUM!` instead of a value.
+    /// Any valid pair of dates currently succeeds, with reversed dates swapped as in `yearfrac`;
+    /// see [`DayCountConvention::try_yearfrac`] to reject them.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    /// assert_eq!(DayCountConvention::Act360.yearfrac_checked(start, end).unwrap(), 181.0 / 360.0);
+    /// ```
+    /// # Errors
+    /// None yet, reserved for future validation.
+    pub fn yearfrac_checked(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<f64, DayCountConventionError> {
+        Ok(self.yearfrac(start, end))
+    }
     /// Signed version of yearfrac function.
     /// Returns negative value if start > end
+    ///
+    /// The magnitude is always computed on the chronologically ordered dates, so conventions
+    /// whose basis depends on the dates (ActAct, ActActISDA) stay exactly antisymmetric:
+    /// `yearfrac_signed(b, a) == -yearfrac_signed(a, b)`.
     /// # Examples
     /// ```rust
     /// use yearfrac::DayCountConvention;
@@ -195,6 +621,430 @@ impl DayCountConvention {
             self.yearfrac(start, end)
         }
     }
+    /// Single precision [`DayCountConvention::yearfrac`], for storing large grids of fractions.
+    /// Day counts and fixed bases are whole numbers (or 365.25) and convert to `f32` exactly;
+    /// the only rounding is the final `f32` division, plus the averaged ActAct basis being
+    /// rounded to `f32` first. ActActISDA sums its per-year fractions in `f32`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// let yf = DayCountConvention::Act360.yearfrac_f32(start, end);
+    /// assert_eq!(yf, 15419.0 / 360.0);
+    /// ```
+    pub fn yearfrac_f32(&self, mut start: NaiveDate, mut end: NaiveDate) -> f32 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        if let DayCountConvention::ActActISDA = self {
+            return isda_year_segments(start, end)
+                .map(|(days, days_in_year)| days as f32 / days_in_year as f32)
+                .sum();
+        }
+        self.diff_dts(start, end) as f32 / self.basis(start, end) as f32
+    }
+    /// Year fraction as an exact fraction `(numerator, denominator)` in lowest terms,
+    /// so long schedules can be summed without rounding and divided once at the end.
+    /// For the fixed-basis conventions this is the day count over the basis. For ActAct it is
+    /// the same averaged basis `yearfrac` uses, e.g. `days * years / total days in those years`,
+    /// and for ActActISDA the per-year parts over a common denominator.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+    /// assert_eq!(DayCountConvention::Act360.yearfrac_ratio(start, end), (1, 4));
+    /// ```
+    pub fn yearfrac_ratio(&self, mut start: NaiveDate, mut end: NaiveDate) -> (i64, i64) {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let (numerator, denominator) = match self {
+            DayCountConvention::ActAct => {
+                let days = (end - start).num_days();
+                let basis = self.basis(start, end);
+                if basis == float::round(basis) {
+                    (days, basis as i64)
+                } else {
+                    // averaged over `years` calendar years: basis = total / years
+                    let years = (end.year() - start.year() + 1) as i64;
+                    (days * years, float::round(basis * years as f64) as i64)
+                }
+            }
+            DayCountConvention::ActActISDA => {
+                // 133590 = 365 * 366
+                let numerator = isda_year_segments(start, end)
+                    .map(|(days, days_in_year)| days * (133_590 / days_in_year))
+                    .sum();
+                (numerator, 133_590)
+            }
+            DayCountConvention::Act365_25 => ((end - start).num_days() * 4, 1461),
+            _ => (
+                self.diff_dts(start, end) as i64,
+                self.basis(start, end) as i64,
+            ),
+        };
+        let gcd = gcd(numerator, denominator);
+        (numerator / gcd, denominator / gcd)
+    }
+    /// [`DayCountConvention::yearfrac`] for any `Datelike`, e.g. `NaiveDateTime` or `DateTime<Utc>`.
+    /// Only the calendar date is used, the time of day is ignored.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::{TimeZone, Utc};
+    /// let start = Utc.with_ymd_and_hms(2021, 1, 1, 23, 59, 59).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2021, 7, 1, 0, 0, 1).unwrap();
+    /// assert_eq!(DayCountConvention::US30360.yearfrac_dt(start, end), 0.5);
+    /// ```
+    pub fn yearfrac_dt<D: Datelike>(&self, start: D, end: D) -> f64 {
+        let to_naive = |date: D| {
+            NaiveDate::from_ymd_opt(date.year(), date.month(), date.day())
+                .expect("yearfrac: Datelike fields always form a valid date")
+        };
+        self.yearfrac(to_naive(start), to_naive(end))
+    }
+    /// [`DayCountConvention::yearfrac`] of each `(start, end)` pair, in one allocation.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let d = |m| NaiveDate::from_ymd_opt(2021, m, 1).unwrap();
+    /// let yfs = DayCountConvention::US30360.yearfrac_many(&[(d(1), d(7)), (d(7), d(4))]);
+    /// assert_eq!(yfs, vec![0.5, 0.25]);
+    /// ```
+    pub fn yearfrac_many(&self, pairs: &[(NaiveDate, NaiveDate)]) -> Vec<f64> {
+        pairs
+            .iter()
+            .map(|(start, end)| self.yearfrac(*start, *end))
+            .collect()
+    }
+    /// Year fraction as a fixed-point integer with `scale` decimal places,
+    /// i.e. `round(yearfrac * 10^scale)`. Rounding is half-up (the fraction is never negative).
+    /// Saturates at `i64::MAX` if the scaled value does not fit.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// let yf = DayCountConvention::US30360.yearfrac_scaled(start, end, 6);
+    /// assert_eq!(yf, 42_213_889);
+    /// ```
+    pub fn yearfrac_scaled(&self, start: NaiveDate, end: NaiveDate, scale: u32) -> i64 {
+        // 10^i32::MAX is already infinite, so clamping keeps the saturation
+        let scale = i32::try_from(scale).unwrap_or(i32::MAX);
+        float::round(self.yearfrac(start, end) * float::powi(10.0, scale)) as i64
+    }
+    /// Year fraction floored at `min` and capped at `max`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let yf = DayCountConvention::US30360.yearfrac_clamped(start, end, 0.25, 1.0);
+    /// assert_eq!(yf, 1.0);
+    /// ```
+    /// # Panics
+    /// If `min > max` or either bound is NaN.
+    pub fn yearfrac_clamped(&self, start: NaiveDate, end: NaiveDate, min: f64, max: f64) -> f64 {
+        assert!(
+            min <= max,
+            "yearfrac: clamp needs min <= max, got {min} > {max}"
+        );
+        self.yearfrac(start, end).clamp(min, max)
+    }
+    /// Year fraction converted back to a whole number of days over the basis,
+    /// i.e. `round(yearfrac * basis)`. That is the calendar day count for the actual-day
+    /// conventions and the adjusted day count for 30/360.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// assert_eq!(DayCountConvention::Act365.round_to_days(start, end), 31);
+    /// assert_eq!(DayCountConvention::EU30360.round_to_days(start, end), 32);
+    /// ```
+    pub fn round_to_days(&self, mut start: NaiveDate, mut end: NaiveDate) -> i64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        float::round(self.yearfrac(start, end) * self.basis(start, end)) as i64
+    }
+    /// Splits year fraction into whole years and the fractional remainder.
+    /// Whole years are counted by advancing `start` one year at a time while the anniversary
+    /// does not pass `end`, capped at the whole years of the fraction itself so the remainder
+    /// is never negative (e.g. 359/360 from Feb 29 to the Feb 28 anniversary under EU 30/360).
+    /// Both parts sum to `yearfrac`.
+    /// The remainder can exceed 1 for bases under 365 days, e.g. 365 actual days short of
+    /// the first anniversary are `(0, 365 / 360)` under Act/360.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+    /// let (years, rem) = DayCountConvention::Act365.split_years(start, end);
+    /// assert_eq!(years, 3);
+    /// assert!((rem - 91.0 / 365.0).abs() < 1e-9);
+    /// ```
+    pub fn split_years(&self, mut start: NaiveDate, mut end: NaiveDate) -> (i64, f64) {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let mut years = 0;
+        // chrono's range is well under i32::MAX / 12 years
+        while schedule::checked_add_months(start, 12 * (years + 1))
+            .is_some_and(|anniversary| anniversary <= end)
+        {
+            years += 1;
+        }
+        let yf = self.yearfrac(start, end);
+        // yearfrac is non-negative, so truncating is flooring
+        let years = (years as i64).min(yf as i64);
+        (years, yf - years as f64)
+    }
+    /// Year fraction minus the difference of the calendar years, e.g. why 1978-02-28 to
+    /// 2020-05-17 shows as 42.21 under US 30/360: 42 calendar years plus 0.21.
+    /// Unlike [`DayCountConvention::split_years`] this subtracts calendar years, not whole years of the fraction,
+    /// so the result can be negative (Dec 2020 to Jan 2021 gives about -0.92).
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    /// let excess = DayCountConvention::US30360.excess_over_whole_years(start, end);
+    /// assert!((excess - 0.21388888889).abs() < 1e-9);
+    /// ```
+    pub fn excess_over_whole_years(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        self.yearfrac(start, end) - (end.year() - start.year()) as f64
+    }
+    /// Year fraction under each of the NASD methods 0-3, indexed by method.
+    /// Handy to see how February and 31st handling differs between methods.
+    /// For conventions other than US30360 every entry equals `yearfrac`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let yfs = DayCountConvention::US30360.nasd_methods_all(start, end);
+    /// assert!((yfs[0] - 43.0 / 360.0).abs() < 1e-9);
+    /// assert!((yfs[3] - 45.0 / 360.0).abs() < 1e-9);
+    /// ```
+    pub fn nasd_methods_all(&self, mut start: NaiveDate, mut end: NaiveDate) -> [f64; 4] {
+        if *self != DayCountConvention::US30360 {
+            return [self.yearfrac(start, end); 4];
+        }
+        if start > end {
+            (start, end) = (end, start)
+        }
+        [0, 1, 2, 3].map(|method| self.nasd360(start, end, method, true) / 360.0)
+    }
+
+    /// Year fraction of the part of `[start, end]` that falls within `year`.
+    /// Actual conventions count those days over the length of `year` (366 in a leap year),
+    /// 30/360 conventions use their usual fraction of the clipped period.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2019, 10, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 4, 1).unwrap();
+    /// let yf = DayCountConvention::Act365.leap_year_fraction(2020, start, end);
+    /// assert!((yf - 91.0 / 366.0).abs() < 1e-9);
+    /// ```
+    pub fn leap_year_fraction(&self, year: i32, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let year_start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or(NaiveDate::MIN);
+        let year_end = NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap_or(NaiveDate::MAX);
+        let (start, end) = (start.max(year_start), end.min(year_end));
+        if start >= end {
+            return 0.0;
+        }
+        match self.family() {
+            ConventionFamily::Thirty360 | ConventionFamily::Business => self.yearfrac(start, end),
+            ConventionFamily::Actual => (end - start).num_days() as f64 / days_in_year(year) as f64,
+        }
+    }
+
+    /// Denominator for the conventions where it is a constant, `None` for ActAct and ActActISDA
+    /// whose year length depends on the dates. Usable in `const` contexts.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// const ACT360_RECIPROCAL: f64 = 1.0 / 360.0;
+    /// const BASIS: Option<f64> = DayCountConvention::Act360.fixed_basis();
+    /// assert_eq!(BASIS.map(|b| 1.0 / b), Some(ACT360_RECIPROCAL));
+    /// assert_eq!(DayCountConvention::ActAct.fixed_basis(), None);
+    /// ```
+    pub const fn fixed_basis(&self) -> Option<f64> {
+        match self {
+            DayCountConvention::US30360
+            | DayCountConvention::Act360
+            | DayCountConvention::EU30360 => Some(360.0),
+            DayCountConvention::Act365 => Some(365.0),
+            DayCountConvention::Bus252 => Some(252.0),
+            DayCountConvention::Act365_25 => Some(365.25),
+            DayCountConvention::ActAct | DayCountConvention::ActActISDA => None,
+        }
+    }
+
+    /// Nominal year length of the convention, usable as a sort key: the fixed basis where
+    /// there is one, and 365.25 for ActAct and ActActISDA. A nominal value, not the basis
+    /// actually applied to a given period, see [`DayCountConvention::year_basis`] for that.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// let mut convs = DayCountConvention::all();
+    /// convs.sort_by(|a, b| a.nominal_basis().total_cmp(&b.nominal_basis()));
+    /// assert_eq!(convs[0], DayCountConvention::Bus252);
+    /// ```
+    pub const fn nominal_basis(&self) -> f64 {
+        match self.fixed_basis() {
+            Some(basis) => basis,
+            None => 365.25,
+        }
+    }
+
+    /// Smallest nonzero year fraction the convention produces, i.e. one day over the basis.
+    /// For ActAct and ActActISDA, whose basis varies, one day over the longest (366 day) year.
+    /// Useful to pick comparison tolerances.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// assert_eq!(DayCountConvention::Act360.min_nonzero_fraction(), 1.0 / 360.0);
+    /// ```
+    pub fn min_nonzero_fraction(&self) -> f64 {
+        match self {
+            DayCountConvention::US30360
+            | DayCountConvention::Act360
+            | DayCountConvention::EU30360 => 1.0 / 360.0,
+            DayCountConvention::Act365 => 1.0 / 365.0,
+            DayCountConvention::Bus252 => 1.0 / 252.0,
+            DayCountConvention::Act365_25 => 1.0 / 365.25,
+            DayCountConvention::ActAct | DayCountConvention::ActActISDA => 1.0 / 366.0,
+        }
+    }
+
+    /// Denominator (days in a year) the convention applies to the period.
+    /// Fixed for all conventions except ActAct, where it depends on the dates:
+    /// the length of the year within a single year, 365 or 366 for periods up to a year
+    /// depending on whether a Feb 29 is covered, and the average year length otherwise.
+    /// For ActActISDA it is the actual days over the year fraction.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2019, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+    /// assert_eq!(DayCountConvention::ActAct.effective_year_length(start, end), 365.5);
+    /// assert_eq!(DayCountConvention::Act360.effective_year_length(start, end), 360.0);
+    /// ```
+    pub fn effective_year_length(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        self.basis(start, end)
+    }
+
+    /// Numerator of the year fraction: the day count after the convention's adjustments
+    /// (30/360 rules), actual days, or business days for Bus252. Dates are swapped if `start > end`, as in `yearfrac`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// let conv = DayCountConvention::US30360;
+    /// assert_eq!(conv.day_count(start, end), 60.0);
+    /// assert_eq!(conv.year_basis(start, end), 360.0);
+    /// assert_eq!(
+    ///     conv.day_count(start, end) / conv.year_basis(start, end),
+    ///     conv.yearfrac(start, end)
+    /// );
+    /// ```
+    pub fn day_count(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        self.diff_dts(start, end)
+    }
+
+    /// Signed whole-day version of [`DayCountConvention::day_count`]: the 30/360-adjusted day
+    /// difference for US30360 and EU30360, actual days for the Act conventions and business
+    /// days for Bus252. Negative if `start > end`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// assert_eq!(DayCountConvention::EU30360.adjusted_days(start, end), 60);
+    /// assert_eq!(DayCountConvention::Act365.adjusted_days(end, start), -59);
+    /// ```
+    pub fn adjusted_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        let days = self.day_count(start, end) as i64;
+        if start > end {
+            -days
+        } else {
+            days
+        }
+    }
+
+    /// Calendar days between the dates as an integer, whatever the convention: the numerator
+    /// of the Act conventions without the division. Dates are swapped if `start > end`, as in
+    /// `yearfrac`. See [`DayCountConvention::adjusted_days`] for the 30/360 or business day count.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// assert_eq!(DayCountConvention::Act360.actual_days(start, end), 59);
+    /// assert_eq!(DayCountConvention::US30360.actual_days(end, start), 59);
+    /// ```
+    pub fn actual_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        (end - start).num_days().abs()
+    }
+
+    /// Denominator of the year fraction, same as [`DayCountConvention::effective_year_length`].
+    /// See [`DayCountConvention::day_count`] for the numerator.
+    pub fn year_basis(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.effective_year_length(start, end)
+    }
+
+    /// Family of the convention: 30/360 or actual days.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{ConventionFamily, DayCountConvention};
+    /// assert_eq!(DayCountConvention::EU30360.family(), ConventionFamily::Thirty360);
+    /// assert_eq!(DayCountConvention::ActAct.family(), ConventionFamily::Actual);
+    /// ```
+    pub fn family(&self) -> ConventionFamily {
+        match self {
+            DayCountConvention::US30360 | DayCountConvention::EU30360 => {
+                ConventionFamily::Thirty360
+            }
+            DayCountConvention::ActAct
+            | DayCountConvention::Act360
+            | DayCountConvention::Act365
+            | DayCountConvention::ActActISDA
+            | DayCountConvention::Act365_25 => ConventionFamily::Actual,
+            DayCountConvention::Bus252 => ConventionFamily::Business,
+        }
+    }
 
     fn basis(&self, start: NaiveDate, end: NaiveDate) -> f64 {
         match self {
@@ -202,68 +1052,93 @@ impl DayCountConvention {
             | DayCountConvention::Act360
             | DayCountConvention::EU30360 => 360.0,
             DayCountConvention::Act365 => 365.0,
-            DayCountConvention::ActAct => {
-                let (start_day, start_month, start_year) =
-                    (start.day(), start.month(), start.year());
-                let (end_day, end_month, end_year) = (end.day(), end.month(), end.year());
-                if start_year == end_year {
-                    if is_leap_year(start_year) {
-                        366.0
-                    } else {
-                        365.0
-                    }
-                } else if (end_year - 1 == start_year)
-                    & ((start_month > end_month)
-                        | ((start_month == end_month) & (start_day > end_day)))
-                {
-                    if is_leap_year(start_year) {
-                        if (start_month < 2) | ((start_month == 2) & (start_day <= 29)) {
-                            366.0
-                        } else {
-                            365.0
-                        }
-                    } else if is_leap_year(end_year) {
-                        if (end_month > 2) | ((end_month == 2) & (end_day == 29)) {
-                            366.0
-                        } else {
-                            365.0
-                        }
-                    } else {
-                        365.0
-                    }
+            DayCountConvention::Bus252 => 252.0,
+            DayCountConvention::Act365_25 => 365.25,
+            DayCountConvention::ActAct => self.excel_act_act_basis(start, end),
+            DayCountConvention::ActActISDA => {
+                let days = (end - start).num_days() as f64;
+                if days == 0.0 {
+                    days_in_year(start.year()) as f64
+                } else {
+                    days / self.act_act_isda(start, end)
+                }
+            }
+        }
+    }
+
+    /// Excel's Act/Act denominator, see [`DayCountConvention::effective_year_length`]
+    fn excel_act_act_basis(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        let (start_day, start_month, start_year) = (start.day(), start.month(), start.year());
+        let (end_day, end_month, end_year) = (end.day(), end.month(), end.year());
+        if start_year == end_year {
+            days_in_year(start_year) as f64
+        } else if (end_year - 1 == start_year)
+            // at most one year apart: Jan 1 2020 to Jan 1 2021 is 366 / 366 = 1.0
+            // (while Jan 1 2020 to Dec 31 2020 stays in the same year branch, 365 / 366)
+            & ((start_month > end_month)
+                | ((start_month == end_month) & (start_day >= end_day)))
+        {
+            if is_leap_year(start_year) {
+                if (start_month < 2) | ((start_month == 2) & (start_day <= 29)) {
+                    366.0
                 } else {
-                    let mut tmp = 0.0;
-                    for i_y in start_year..end_year + 1 {
-                        if is_leap_year(i_y) {
-                            tmp += 366.0
-                        } else {
-                            tmp += 365.0
-                        }
-                    }
-                    tmp / (end_year as f64 - start_year as f64 + 1.0)
+                    365.0
                 }
+            } else if is_leap_year(end_year) {
+                if (end_month > 2) | ((end_month == 2) & (end_day == 29)) {
+                    366.0
+                } else {
+                    365.0
+                }
+            } else {
+                365.0
             }
+        } else {
+            days_in_years(start_year, end_year) as f64 / (end_year as f64 - start_year as f64 + 1.0)
         }
     }
 
+    /// ISDA Act/Act year fraction: the period is split at each Jan 1 it crosses and the days
+    /// in every calendar year are divided by that year's length.
+    fn act_act_isda(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        isda_year_segments(start, end)
+            .map(|(days, days_in_year)| days as f64 / days_in_year as f64)
+            .sum()
+    }
+
     fn diff_dts(&self, start: NaiveDate, end: NaiveDate) -> f64 {
         match self {
             DayCountConvention::ActAct
             | DayCountConvention::Act360
-            | DayCountConvention::Act365 => (end - start).num_days() as f64,
+            | DayCountConvention::Act365
+            | DayCountConvention::ActActISDA
+            | DayCountConvention::Act365_25 => (end - start).num_days() as f64,
+            DayCountConvention::Bus252 => business_days(start, end, &[]) as f64,
             DayCountConvention::US30360 => self.nasd360(start, end, 0, true),
             DayCountConvention::EU30360 => self.euro360(start, end),
         }
     }
 
     fn euro360(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.euro360_with(start, end, |_| {})
+    }
+
+    /// `euro360`, reporting each day adjustment to `record`
+    fn euro360_with(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        mut record: impl FnMut(&'static str),
+    ) -> f64 {
         let (mut start_day, start_month, start_year) = (start.day(), start.month(), start.year());
         let (mut end_day, end_month, end_year) = (end.day(), end.month(), end.year());
         if start_day == 31 {
             start_day = 30;
+            record("start_31_to_30");
         };
         if end_day == 31 {
             end_day = 30;
+            record("end_31_to_30");
         };
         self.days360(
             start_day,
@@ -275,24 +1150,47 @@ impl DayCountConvention {
         )
     }
 
-    /// NASD360 Needs work on methods (currently only Excel's third method)
+    /// NASD 30/360 day count. Method 0 (with `use_eom`) is Excel's YEARFRAC basis 0:
+    /// the end of February rule only fires when both dates are the last day of February,
+    /// and an end on the 31st only rolls to 30 when the start is on the 30th or 31st.
+    /// Methods 1 and 2 are the same as 0. Method 3 rolls an end on the last day of February
+    /// or on the 31st to 30 regardless of the start.
     fn nasd360(&self, start: NaiveDate, end: NaiveDate, method: u8, use_eom: bool) -> f64 {
+        self.nasd360_with(start, end, method, use_eom, true, |_| {})
+    }
+
+    /// `nasd360`, reporting each day adjustment to `record`.
+    /// `eom_february` switches the end of February rule for the end date off when false.
+    fn nasd360_with(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        method: u8,
+        use_eom: bool,
+        eom_february: bool,
+        mut record: impl FnMut(&'static str),
+    ) -> f64 {
         let (mut start_day, start_month, start_year) = (start.day(), start.month(), start.year());
         let (mut end_day, end_month, end_year) = (end.day(), end.month(), end.year());
-        if ((end_month == 2) & is_end_of_month(end_day, end_month, end_year))
+        if eom_february
+            & ((end_month == 2) & is_end_of_month(end_day, end_month, end_year))
             & (((start_month == 2) & is_end_of_month(start_day, start_month, start_year))
                 | (method == 3))
         {
             end_day = 30;
+            record("end_feb_eom_to_30");
         };
         if (end_day == 31) & ((start_day >= 30) | (method == 3)) {
             end_day = 30;
+            record("end_31_to_30");
         };
         if start_day == 31 {
             start_day = 30;
+            record("start_31_to_30");
         }
         if use_eom & (start_month == 2) & is_end_of_month(start_day, start_month, start_year) {
             start_day = 30;
+            record("start_feb_eom_to_30");
         }
         self.days360(
             start_day,
@@ -313,41 +1211,169 @@ impl DayCountConvention {
         end_month: u32,
         end_year: i32,
     ) -> f64 {
-        ((end_year - start_year) * 360
-            + (end_month as i32 - start_month as i32) * 30
-            + (end_day as i32 - start_day as i32))
-            .into()
+        // i64 so no range of years can overflow, chrono's or not
+        ((end_year as i64 - start_year as i64) * 360
+            + (end_month as i64 - start_month as i64) * 30
+            + (end_day as i64 - start_day as i64)) as f64
     }
 }
 
 impl FromStr for DayCountConvention {
     type Err = DayCountConventionError;
 
+    /// Case-insensitive, ignoring surrounding whitespace
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "nasd30/360" => Ok(DayCountConvention::US30360),
-            "act/act" => Ok(DayCountConvention::ActAct),
-            "act360" => Ok(DayCountConvention::Act360),
-            "act365" => Ok(DayCountConvention::Act365),
-            "eur30/360" => Ok(DayCountConvention::EU30360),
-            other => Err(DayCountConventionError::InvalidValue {
-                val: other.to_owned(),
-            }),
-        }
+        let token = s.trim().to_lowercase();
+        DayCountConvention::all()
+            .into_iter()
+            .find(|conv| conv.aliases().contains(&token.as_str()))
+            .ok_or_else(|| DayCountConventionError::InvalidValue { val: s.to_owned() })
     }
 }
 
-impl Default for DayCountConvention {
-    fn default() -> Self {
-        DayCountConvention::US30360
+/// Same as [`DayCountConvention::from_int`]
+impl TryFrom<u8> for DayCountConvention {
+    type Error = DayCountConventionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_int(value)
+    }
+}
+
+/// Same as [`DayCountConvention::from_str`]
+impl TryFrom<&str> for DayCountConvention {
+    type Error = DayCountConventionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Writes the `from_str` token, e.g. "nasd30/360"
+impl fmt::Display for DayCountConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.short_name())
     }
 }
 
 #[derive(Debug, Error)]
 pub enum DayCountConventionError {
-    #[error("Yearfrac: Invalid Value: {}. Has to be one of: nasd30/360, act/act, act360, act365, eur30/360 (from_str) 
-    or in the range 0-4 (from_int).", val)]
+    #[error("Yearfrac: Invalid Value: {}. {}Has to be one of: {} (from_str) 
+    or in the range 0-{} (from_int).", val, did_you_mean(val), DayCountConvention::possible_values().join(", "), DayCountConvention::all().len() - 1)]
     InvalidValue { val: String },
+    #[error(
+        "Yearfrac: Invalid Value: {} at index {}. Has to be in the range 0-{}.",
+        val,
+        index,
+        DayCountConvention::all().len() - 1
+    )]
+    InvalidValueAt { index: usize, val: String },
+    #[error(
+        "Yearfrac: Invalid Excel Basis: {}. Has to be in the range 0-4 (Excel YEARFRAC basis).",
+        val
+    )]
+    InvalidExcelBasis { val: u8 },
+    #[error(
+        "Yearfrac: Invalid Tenor: {}. Has to be a number followed by D, W, M or Y, e.g. 6M.",
+        val
+    )]
+    InvalidTenor { val: String },
+    #[error("Yearfrac: Invalid Input: {}.", reason)]
+    InvalidInput { reason: &'static str },
+    #[error(
+        "Yearfrac: Non-finite Input: {} = {}. Has to be a finite number.",
+        name,
+        val
+    )]
+    NonFiniteInput { name: &'static str, val: f64 },
+    #[error(
+        "Yearfrac: Non-business Day: {}. Has to be a weekday which is not a holiday.",
+        date
+    )]
+    NonBusinessDay { date: NaiveDate },
+    #[error(
+        "Yearfrac: No Convergence: solver stopped after {} iterations.",
+        iterations
+    )]
+    NoConvergence { iterations: usize },
+    #[error(
+        "Yearfrac: Start After End: {} > {}. Has to be start <= end.",
+        start,
+        end
+    )]
+    StartAfterEnd { start: NaiveDate, end: NaiveDate },
+}
+
+/// Greatest common divisor, 1 if both are 0
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a == 0 {
+        1
+    } else {
+        a.abs()
+    }
+}
+
+/// "Did you mean X? " for the canonical token closest to `val` by Levenshtein distance,
+/// if it is at most 2 edits away, otherwise empty
+fn did_you_mean(val: &str) -> String {
+    let val = val.trim().to_lowercase();
+    DayCountConvention::all()
+        .iter()
+        .map(|conv| (levenshtein(&val, conv.aliases()[0]), conv))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map_or_else(String::new, |(_, conv)| {
+            alloc::format!("Did you mean {conv}? ")
+        })
+}
+
+/// Number of single character insertions, deletions or substitutions turning `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Days in the calendar years `first..=last` in constant time, so long ActAct spans
+/// cost the same as short ones.
+fn days_in_years(first: i32, last: i32) -> i64 {
+    // leap years up to `year`; Euclidean division keeps years <= 0 proleptic like is_leap_year
+    let leaps = |year: i64| year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400);
+    let (first, last) = (first as i64, last as i64);
+    365 * (last - first + 1) + leaps(last) - leaps(first - 1)
+}
+
+/// Splits `[start, end)` at each January 1st, yielding `(days, days_in_year)` for the part
+/// of every calendar year it covers, assuming `start <= end`. The ActActISDA day count.
+fn isda_year_segments(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = (i64, i64)> {
+    let mut from = start;
+    (start.year()..=end.year()).map(move |year| {
+        let to = NaiveDate::from_ymd_opt(year + 1, 1, 1).map_or(end, |next| next.min(end));
+        let days = (to - from).num_days();
+        from = to;
+        (days, days_in_year(year) as i64)
+    })
+}
+
+/// Rejects NaN and infinite inputs of the financial helpers
+pub(crate) fn check_finite(name: &'static str, val: f64) -> Result<(), DayCountConventionError> {
+    if val.is_finite() {
+        Ok(())
+    } else {
+        Err(DayCountConventionError::NonFiniteInput { name, val })
+    }
 }
 
 #[cfg(test)]
@@ -358,4 +1384,26 @@ mod tests {
     fn test_default() {
         assert_eq!(DayCountConvention::default(), DayCountConvention::US30360)
     }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("act/365", "act365"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "act360"), 6);
+        assert_eq!(levenshtein("act360", "act360"), 0);
+    }
+
+    #[test]
+    fn test_days_in_years() {
+        for (first, last) in [
+            (1978, 2020),
+            (2020, 2020),
+            (1899, 1901),
+            (-401, 3),
+            (1, 2400),
+        ] {
+            let summed: u32 = (first..=last).map(days_in_year).sum();
+            assert_eq!(days_in_years(first, last), summed as i64);
+        }
+    }
 }
@@ -0,0 +1,357 @@
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{ConventionFamily, DayCountConvention, DayCountConventionError};
+
+/// How often a coupon is paid.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Frequency {
+    Annual,
+    SemiAnnual,
+    Quarterly,
+    Monthly,
+}
+
+impl Frequency {
+    /// Number of coupon periods in a year.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::Frequency;
+    /// assert_eq!(Frequency::SemiAnnual.per_year(), 2);
+    /// assert_eq!(Frequency::Monthly.per_year(), 12);
+    /// ```
+    pub const fn per_year(&self) -> u32 {
+        match self {
+            Frequency::Annual => 1,
+            Frequency::SemiAnnual => 2,
+            Frequency::Quarterly => 4,
+            Frequency::Monthly => 12,
+        }
+    }
+}
+
+/// Shifts `date` by a number of calendar months, rolling to the last day of
+/// the target month when the day does not exist (e.g. Jan 31 + 1M = Feb 28).
+/// # Examples
+/// ```rust
+/// use chrono::NaiveDate;
+/// use yearfrac::add_months;
+/// let dt = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+/// assert_eq!(add_months(dt, 1), NaiveDate::from_ymd_opt(2021, 2, 28).unwrap());
+/// assert_eq!(add_months(dt, -2), NaiveDate::from_ymd_opt(2020, 11, 30).unwrap());
+/// ```
+/// # Panics
+/// If the resulting date is out of chrono's supported range.
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    checked_add_months(date, months).expect("yearfrac: add_months result out of range")
+}
+
+pub(crate) fn checked_add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    }
+}
+
+/// Checks that consecutive `dates` are `months_per_period` months apart,
+/// give or take `tolerance_days`. Catches missing or duplicated coupon dates.
+/// # Examples
+/// ```rust
+/// use chrono::NaiveDate;
+/// use yearfrac::validate_schedule;
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2021, 1, 15).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 15).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 1, 17).unwrap(),
+/// ];
+/// assert!(validate_schedule(&dates, 6, 3).is_ok());
+/// assert!(validate_schedule(&dates, 3, 3).is_err());
+/// ```
+/// # Errors
+/// A description naming the first offending pair of dates.
+pub fn validate_schedule(
+    dates: &[NaiveDate],
+    months_per_period: u32,
+    tolerance_days: i64,
+) -> Result<(), String> {
+    // Expected dates are rolled from the first one, so month-end schedules don't drift
+    for (k, pair) in dates.windows(2).enumerate() {
+        let (prev, next) = (pair[0], pair[1]);
+        let expected = i32::try_from(months_per_period as u64 * (k as u64 + 1))
+            .ok()
+            .and_then(|months| checked_add_months(dates[0], months))
+            .ok_or_else(|| format!("{prev} -> {next}: expected date is out of range"))?;
+        let off_by = (next - expected).num_days();
+        if off_by.abs() > tolerance_days {
+            return Err(format!(
+                "{prev} -> {next}: expected {expected} (+/- {tolerance_days} days), off by {off_by} days"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses a tenor like "90D", "2W", "6M" or "1Y" and applies it to `start`.
+fn apply_tenor(start: NaiveDate, tenor: &str) -> Option<NaiveDate> {
+    let unit_at = tenor.len().checked_sub(1)?;
+    if !tenor.is_char_boundary(unit_at) {
+        return None;
+    }
+    let (count, unit) = tenor.split_at(unit_at);
+    if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let count: i32 = count.parse().ok()?;
+    match unit {
+        "D" | "d" => start.checked_add_signed(Duration::days(count.into())),
+        "W" | "w" => start.checked_add_signed(Duration::weeks(count.into())),
+        "M" | "m" => checked_add_months(start, count),
+        "Y" | "y" => checked_add_months(start, count.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// Act/Act ICMA, the convention of most government bonds: actual days of `[start, end]`
+/// over `frequency` times the actual days of the coupon period `[period_start, period_end]`
+/// holding it. A full regular period is exactly `1 / frequency`.
+/// Long or multi-period stubs have to be split into coupon periods by the caller.
+/// # Examples
+/// ```rust
+/// use yearfrac::yearfrac_icma;
+/// use chrono::NaiveDate;
+/// let period_start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+/// let period_end = NaiveDate::from_ymd_opt(2021, 7, 15).unwrap();
+/// let yf = yearfrac_icma(period_start, period_end, period_start, period_end, 2);
+/// assert_eq!(yf, 0.5);
+/// ```
+/// # Panics
+/// If `frequency` is 0, the period is empty or `[start, end]` is not inside it.
+pub fn yearfrac_icma(
+    mut start: NaiveDate,
+    mut end: NaiveDate,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    frequency: u32,
+) -> f64 {
+    if start > end {
+        (start, end) = (end, start)
+    }
+    assert!(
+        frequency > 0,
+        "yearfrac: ICMA needs a coupon frequency above 0"
+    );
+    assert!(
+        period_start < period_end,
+        "yearfrac: ICMA needs period_start < period_end, got {period_start} >= {period_end}"
+    );
+    assert!(
+        period_start <= start && end <= period_end,
+        "yearfrac: ICMA needs {start} - {end} inside the coupon period {period_start} - {period_end}"
+    );
+    let days = (end - start).num_days() as f64;
+    let period_days = (period_end - period_start).num_days() as f64;
+    days / (frequency as f64 * period_days)
+}
+
+impl DayCountConvention {
+    /// Calculates year fruction from `start` to `start` + `tenor`.
+    /// Tenor is a positive count followed by one of D(ays), W(eeks), M(onths) or Y(ears),
+    /// e.g. "90D", "6M", "2Y". Months and years roll to month end like [`add_months`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let yf = DayCountConvention::US30360.yearfrac_tenor(start, "6M").unwrap();
+    /// assert!((yf - 0.5).abs() < 1e-9);
+    /// ```
+    pub fn yearfrac_tenor(
+        &self,
+        start: NaiveDate,
+        tenor: &str,
+    ) -> Result<f64, DayCountConventionError> {
+        let end =
+            apply_tenor(start, tenor).ok_or_else(|| DayCountConventionError::InvalidTenor {
+                val: tenor.to_owned(),
+            })?;
+        Ok(self.yearfrac(start, end))
+    }
+
+    /// Snaps `date` to the nearest period boundary of the schedule generated
+    /// from `anchor` in steps of `months_per_period` months (in either direction).
+    /// Distance is measured in year fractions of this convention; ties go to the earlier boundary.
+    /// A `months_per_period` of 0 generates no schedule and returns `date` unchanged.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let anchor = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let date = NaiveDate::from_ymd_opt(2021, 6, 1).unwrap();
+    /// let snapped = DayCountConvention::Act365.round_to_period(anchor, date, 3);
+    /// assert_eq!(snapped, NaiveDate::from_ymd_opt(2021, 7, 15).unwrap());
+    /// ```
+    pub fn round_to_period(
+        &self,
+        anchor: NaiveDate,
+        date: NaiveDate,
+        months_per_period: u32,
+    ) -> NaiveDate {
+        if months_per_period == 0 {
+            return date;
+        }
+        let step = months_per_period as i32;
+        let boundary = |k: i32| add_months(anchor, k * step);
+        let months_apart =
+            (date.year() - anchor.year()) * 12 + date.month() as i32 - anchor.month() as i32;
+        let mut k = months_apart.div_euclid(step);
+        while boundary(k) > date {
+            k -= 1;
+        }
+        while boundary(k + 1) <= date {
+            k += 1;
+        }
+        let (lower, upper) = (boundary(k), boundary(k + 1));
+        if self.yearfrac(lower, date) <= self.yearfrac(date, upper) {
+            lower
+        } else {
+            upper
+        }
+    }
+
+    /// Lazily walks coupon periods from `issue` to `maturity` in steps of `months_per_period`,
+    /// yielding `(period_start, period_end, yearfrac)`. Period dates are rolled from `issue`
+    /// (so a month-end schedule stays at month end) and the final period is cut short at `maturity`.
+    /// Yields nothing if `months_per_period` is 0 or `issue` is not before `maturity`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let issue = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let maturity = NaiveDate::from_ymd_opt(2022, 1, 15).unwrap();
+    /// let yfs: Vec<f64> = DayCountConvention::US30360
+    ///     .coupon_period_iter(issue, maturity, 6)
+    ///     .map(|(_, _, yf)| yf)
+    ///     .collect();
+    /// assert_eq!(yfs, vec![0.5, 0.5]);
+    /// ```
+    pub fn coupon_period_iter(
+        &self,
+        issue: NaiveDate,
+        maturity: NaiveDate,
+        months_per_period: u32,
+    ) -> impl Iterator<Item = (NaiveDate, NaiveDate, f64)> {
+        let conv = *self;
+        let mut period = 0;
+        let mut start = issue;
+        core::iter::from_fn(move || {
+            if months_per_period == 0 || start >= maturity {
+                return None;
+            }
+            period += 1;
+            let end = i32::try_from(months_per_period as u64 * period)
+                .ok()
+                .and_then(|months| checked_add_months(issue, months))
+                .map_or(maturity, |end| end.min(maturity));
+            let item = (start, end, conv.yearfrac(start, end));
+            start = end;
+            Some(item)
+        })
+    }
+
+    /// Year fraction of one regular coupon period, without needing the period's dates.
+    /// For the 30/360 conventions, ActAct, ActActISDA and Bus252 a regular period is
+    /// `1 / freq.per_year()`. The fixed basis actual conventions give the nominal fraction of
+    /// an average 365.25 day year, the one [`Self::nominal_basis`] uses, e.g. `365.25 / 360 / 2`
+    /// for a semiannual Act/360 period, so Act/365.25 gives exactly `1 / freq.per_year()`.
+    /// The value of a real period depends on its calendar days, see [`Self::coupon_period_iter`]
+    /// for those.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, Frequency};
+    /// assert_eq!(DayCountConvention::US30360.coupon_yearfrac(Frequency::SemiAnnual), 0.5);
+    /// assert_eq!(DayCountConvention::Act365_25.coupon_yearfrac(Frequency::Quarterly), 0.25);
+    /// assert_eq!(
+    ///     DayCountConvention::Act360.coupon_yearfrac(Frequency::SemiAnnual),
+    ///     365.25 / 360.0 / 2.0
+    /// );
+    /// ```
+    pub fn coupon_yearfrac(&self, freq: Frequency) -> f64 {
+        let per_year = freq.per_year() as f64;
+        match (self.family(), self.fixed_basis()) {
+            (ConventionFamily::Actual, Some(basis)) => {
+                DayCountConvention::ActAct.nominal_basis() / basis / per_year
+            }
+            _ => 1.0 / per_year,
+        }
+    }
+
+    /// Number of whole `freq` periods from `start` to `end`, and the year fraction of the
+    /// trailing stub after the last whole period. Periods are rolled from `start` like
+    /// [`add_months`], so a month-end start keeps stepping through month ends.
+    /// Dates are swapped if `start > end`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, Frequency};
+    /// use chrono::NaiveDate;
+    /// let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    /// let conv = DayCountConvention::Act360;
+    /// let (periods, stub) = conv.periods_between(d(2021, 1, 31), d(2021, 9, 15), Frequency::Quarterly);
+    /// // Apr 30 and Jul 31, then Jul 31 - Sep 15
+    /// assert_eq!((periods, stub), (2, 46.0 / 360.0));
+    /// ```
+    pub fn periods_between(
+        &self,
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+        freq: Frequency,
+    ) -> (u32, f64) {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        let step = 12 / freq.per_year() as i32;
+        let mut periods: u32 = 0;
+        let mut last = start;
+        while let Some(next) = periods
+            .checked_add(1)
+            .and_then(|k| i32::try_from(k).ok()?.checked_mul(step))
+            .and_then(|months| checked_add_months(start, months))
+            .filter(|next| *next <= end)
+        {
+            periods += 1;
+            last = next;
+        }
+        (periods, self.yearfrac(last, end))
+    }
+
+    /// Collected [`Self::coupon_period_iter`]: consecutive periods of `months_step` months from
+    /// `start`, each with its year fraction, the last one clamped to `end`.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    /// let periods = DayCountConvention::Act360.schedule_yearfracs(d(2021, 1, 31), d(2021, 3, 15), 1);
+    /// assert_eq!(
+    ///     periods,
+    ///     vec![
+    ///         (d(2021, 1, 31), d(2021, 2, 28), 28.0 / 360.0),
+    ///         (d(2021, 2, 28), d(2021, 3, 15), 15.0 / 360.0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn schedule_yearfracs(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        months_step: u32,
+    ) -> Vec<(NaiveDate, NaiveDate, f64)> {
+        self.coupon_period_iter(start, end, months_step).collect()
+    }
+}
@@ -0,0 +1,246 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::DayCountConvention;
+
+/// 30/360 rule sets selectable through [`DayCountConvention::yearfrac_thirty360`].
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Thirty360Preset {
+    /// Excel's US (NASD) method, same as [`DayCountConvention::US30360`]
+    Us,
+    /// SIA rule, applied in this order:
+    ///
+    /// 1. if the start day is 31 it becomes 30
+    ///
+    /// 2. if the end day is 31 and the (adjusted) start day is 30, the end day becomes 30
+    ///
+    /// Unlike `Us`, there is no special handling of the end of February.
+    Sia,
+    /// European method, same as [`DayCountConvention::EU30360`]
+    European,
+}
+
+/// US (NASD) 30/360 with its end of month rules picked individually.
+/// Built with [`Thirty360Builder`]; the defaults are [`DayCountConvention::US30360`].
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thirty360 {
+    end_of_month: bool,
+    eom_february: bool,
+}
+
+impl Thirty360 {
+    /// A builder starting from [`DayCountConvention::US30360`]'s rules.
+    pub fn builder() -> Thirty360Builder {
+        Thirty360Builder::new()
+    }
+
+    /// Calculates year fruction with the configured rules, same as [`DayCountConvention::yearfrac`].
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::Thirty360;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// let us = Thirty360::builder().build();
+    /// let no_eom = Thirty360::builder().end_of_month(false).build();
+    /// assert_eq!(us.yearfrac(start, end), 31.0 / 360.0);
+    /// assert_eq!(no_eom.yearfrac(start, end), 33.0 / 360.0);
+    /// ```
+    pub fn yearfrac(&self, mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        DayCountConvention::US30360.nasd360_with(
+            start,
+            end,
+            0,
+            self.end_of_month,
+            self.eom_february,
+            |_| {},
+        ) / 360.0
+    }
+}
+
+/// Builder for [`Thirty360`], starting from [`DayCountConvention::US30360`]'s rules.
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thirty360Builder {
+    end_of_month: bool,
+    eom_february: bool,
+}
+
+impl Default for Thirty360Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Thirty360Builder {
+    /// A builder with [`DayCountConvention::US30360`]'s rules, both end of month rules on.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{Thirty360, Thirty360Builder};
+    /// assert_eq!(Thirty360Builder::new(), Thirty360::builder());
+    /// ```
+    pub fn new() -> Self {
+        Thirty360Builder {
+            end_of_month: true,
+            eom_february: true,
+        }
+    }
+
+    /// Roll a start on the last day of February to 30. On by default.
+    pub fn end_of_month(mut self, end_of_month: bool) -> Self {
+        self.end_of_month = end_of_month;
+        self
+    }
+
+    /// Roll an end on the last day of February to 30 when the start is also
+    /// the last day of February. On by default.
+    pub fn eom_february(mut self, eom_february: bool) -> Self {
+        self.eom_february = eom_february;
+        self
+    }
+
+    /// Finishes the configuration.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, Thirty360Builder};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 5, 17).unwrap();
+    /// let us = Thirty360Builder::new().build();
+    /// assert_eq!(us.yearfrac(start, end), DayCountConvention::US30360.yearfrac(start, end));
+    /// ```
+    pub fn build(self) -> Thirty360 {
+        Thirty360 {
+            end_of_month: self.end_of_month,
+            eom_february: self.eom_february,
+        }
+    }
+}
+
+impl DayCountConvention {
+    /// Calculates 30/360 year fruction using the given rule preset.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::{DayCountConvention, Thirty360Preset};
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// let us = DayCountConvention::yearfrac_thirty360(start, end, Thirty360Preset::Us);
+    /// let sia = DayCountConvention::yearfrac_thirty360(start, end, Thirty360Preset::Sia);
+    /// assert!((us - 31.0 / 360.0).abs() < 1e-9);
+    /// assert!((sia - 33.0 / 360.0).abs() < 1e-9);
+    /// ```
+    pub fn yearfrac_thirty360(
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+        preset: Thirty360Preset,
+    ) -> f64 {
+        match preset {
+            Thirty360Preset::Us => DayCountConvention::US30360.yearfrac(start, end),
+            Thirty360Preset::European => DayCountConvention::EU30360.yearfrac(start, end),
+            Thirty360Preset::Sia => {
+                if start > end {
+                    (start, end) = (end, start)
+                }
+                let (mut start_day, start_month, start_year) =
+                    (start.day(), start.month(), start.year());
+                let (mut end_day, end_month, end_year) = (end.day(), end.month(), end.year());
+                if start_day == 31 {
+                    start_day = 30;
+                }
+                if (end_day == 31) & (start_day == 30) {
+                    end_day = 30;
+                }
+                DayCountConvention::US30360.days360(
+                    start_day,
+                    start_month,
+                    start_year,
+                    end_day,
+                    end_month,
+                    end_year,
+                ) / 360.0
+            }
+        }
+    }
+
+    /// Plainest 30/360: every month counts as 30 days with no adjustment of the 31st
+    /// or of the end of February, i.e. `(360 * years + 30 * months + days) / 360`
+    /// straight from the calendar fields.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// let yf = DayCountConvention::yearfrac_accounting_360(start, end);
+    /// assert!((yf - 33.0 / 360.0).abs() < 1e-9);
+    /// ```
+    pub fn yearfrac_accounting_360(mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        DayCountConvention::US30360.days360(
+            start.day(),
+            start.month(),
+            start.year(),
+            end.day(),
+            end.month(),
+            end.year(),
+        ) / 360.0
+    }
+
+    /// 30/365: a hybrid taking the US (NASD) 30/360 day count, including its end of February
+    /// handling, as the numerator and a fixed 365 day year as the denominator.
+    /// Neither [`DayCountConvention::US30360`] (basis 360) nor [`DayCountConvention::Act365`]
+    /// (actual days).
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+    /// let yf = DayCountConvention::yearfrac_30_365(start, end);
+    /// assert!((yf - 60.0 / 365.0).abs() < 1e-9);
+    /// ```
+    pub fn yearfrac_30_365(mut start: NaiveDate, mut end: NaiveDate) -> f64 {
+        if start > end {
+            (start, end) = (end, start)
+        }
+        DayCountConvention::US30360.diff_dts(start, end) / 365.0
+    }
+
+    /// US (NASD) 30/360 with an explicit method, see [`DayCountConvention::nasd_methods_all`].
+    /// `yearfrac_nasd(start, end, 0, true)` is [`DayCountConvention::US30360`], i.e. Excel's
+    /// YEARFRAC basis 0. Methods 1 and 2 give the same result as 0; method 3 also rolls an end
+    /// date on the 31st or on the last day of February to 30 whatever the start date.
+    /// `use_eom` rolls a start on the last day of February to 30.
+    /// # Examples
+    /// ```rust
+    /// use yearfrac::DayCountConvention;
+    /// use chrono::NaiveDate;
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    /// assert_eq!(DayCountConvention::yearfrac_nasd(start, end, 0, true), 43.0 / 360.0);
+    /// assert_eq!(DayCountConvention::yearfrac_nasd(start, end, 3, true), 45.0 / 360.0);
+    /// ```
+    /// # Panics
+    /// If `method` is greater than 3.
+    pub fn yearfrac_nasd(
+        mut start: NaiveDate,
+        mut end: NaiveDate,
+        method: u8,
+        use_eom: bool,
+    ) -> f64 {
+        assert!(
+            method <= 3,
+            "yearfrac: NASD method has to be 0-3, got {method}"
+        );
+        if start > end {
+            (start, end) = (end, start)
+        }
+        DayCountConvention::US30360.nasd360(start, end, method, use_eom) / 360.0
+    }
+}
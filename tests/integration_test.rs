@@ -1,12 +1,22 @@
 use chrono::NaiveDate;
-use yearfrac::DayCountConvention;
+use yearfrac::{
+    add_months, adjust, days_in_month, days_in_year, is_business_day, is_end_of_month,
+    is_leap_year, try_is_end_of_month, validate_schedule, yearfrac_bus252, yearfrac_icma,
+    yearfrac_ymd, BusinessDayConvention, Compounding, ConventionFamily, CustomConvention,
+    DayCountConvention, DayCountConventionError, Frequency, HolidayCalendar, Numerator, Thirty360,
+    Thirty360Builder, Thirty360Preset, WeekendsOnly,
+};
+
+fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, day).unwrap()
+}
 
 #[test]
 fn test_accuracy() {
     let delta = 1e-9;
 
-    let start = NaiveDate::from_ymd(1978, 2, 28);
-    let end = NaiveDate::from_ymd(2020, 5, 17);
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
     let yf = DayCountConvention::from_int(0)
         .unwrap()
         .yearfrac(start, end);
@@ -30,8 +40,8 @@ fn test_accuracy() {
         .yearfrac(start, end);
     assert!((yf - 42.21944444444).abs() < delta);
 
-    let start = NaiveDate::from_ymd(1993, 12, 2);
-    let end = NaiveDate::from_ymd(2022, 4, 18);
+    let start = NaiveDate::from_ymd_opt(1993, 12, 2).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 4, 18).unwrap();
     let yf = DayCountConvention::from_str("nasd30/360")
         .unwrap()
         .yearfrac(start, end);
@@ -62,23 +72,33 @@ fn test_bad_input_str() {
     DayCountConvention::from_str("wrongvalue").unwrap();
 }
 
-/*
 #[test]
-#[should_panic]
-fn test_bad_dates () {
-    let start = NaiveDate::from_ymd(2032, 4, 28);
-    let end = NaiveDate::from_ymd(2022, 5, 17);
-    DayCountConvention::from_str("act/act").unwrap()
-    .yearfrac(start, end);
+fn test_bad_dates() {
+    let start = NaiveDate::from_ymd_opt(2032, 4, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 5, 17).unwrap();
+    let conv = DayCountConvention::from_str("act/act").unwrap();
+
+    let err = conv.try_yearfrac(start, end).unwrap_err();
+    assert!(matches!(
+        err,
+        DayCountConventionError::StartAfterEnd { start: s, end: e } if s == start && e == end
+    ));
+    assert!(err.to_string().contains("2032-04-28 > 2022-05-17"));
+
+    // lenient yearfrac keeps swapping
+    assert_eq!(
+        conv.try_yearfrac(end, start).unwrap(),
+        conv.yearfrac(start, end)
+    );
+    assert_eq!(conv.try_yearfrac(end, end).unwrap(), 0.0);
 }
-*/
 
 #[test]
 fn test_start_after_end() {
     let delta = 1e-9;
 
-    let end = NaiveDate::from_ymd(1978, 2, 28);
-    let start = NaiveDate::from_ymd(2020, 5, 17);
+    let end = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let start = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
     let yf = DayCountConvention::from_int(0)
         .unwrap()
         .yearfrac(start, end);
@@ -89,10 +109,2446 @@ fn test_start_after_end() {
 fn test_yearfrac_signed() {
     let delta = 1e-9;
 
-    let end = NaiveDate::from_ymd(1978, 2, 28);
-    let start = NaiveDate::from_ymd(2020, 5, 17);
+    let end = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let start = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
     let yf = DayCountConvention::from_int(0)
         .unwrap()
         .yearfrac_signed(start, end);
     assert!((yf + 42.21388888889).abs() < delta);
 }
+
+#[test]
+fn test_yearfrac_signed_act_act_symmetry() {
+    // leap year bases differ depending on which side of Feb 29 the dates sit
+    let pairs = [
+        (d(2019, 12, 15), d(2020, 3, 1)),
+        (d(2020, 2, 28), d(2021, 2, 28)),
+        (d(2020, 3, 1), d(2021, 2, 28)),
+        (d(2019, 3, 1), d(2020, 2, 29)),
+        (d(2015, 6, 30), d(2024, 2, 29)),
+    ];
+    for conv in [DayCountConvention::ActAct, DayCountConvention::ActActISDA] {
+        for (a, b) in pairs {
+            let forward = conv.yearfrac_signed(a, b);
+            assert!(forward > 0.0);
+            assert_eq!(conv.yearfrac_signed(b, a), -forward);
+            assert_eq!(forward, conv.yearfrac(a, b));
+        }
+    }
+}
+
+#[test]
+fn test_split_years() {
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+    let conv = DayCountConvention::Act365;
+    let (years, rem) = conv.split_years(start, end);
+    assert_eq!(years, 3);
+    assert!((rem - 0.25).abs() < 1e-2);
+    assert!((years as f64 + rem - conv.yearfrac(start, end)).abs() < 1e-12);
+
+    let (years, rem) = conv.split_years(end, start);
+    assert_eq!(years, 3);
+    assert!((rem - 91.0 / 365.0).abs() < 1e-12);
+
+    let (years, rem) = conv.split_years(start, start);
+    assert_eq!(years, 0);
+    assert_eq!(rem, 0.0);
+}
+
+#[test]
+fn test_split_years_anniversaries() {
+    // 359 days under EU 30/360: the Feb 28 anniversary is reached, a full year is not
+    let (years, rem) = DayCountConvention::EU30360.split_years(d(2020, 2, 29), d(2021, 2, 28));
+    assert_eq!((years, rem), (0, 359.0 / 360.0));
+    // 365 actual days over 360 before the first anniversary, the remainder exceeds 1
+    let (years, rem) = DayCountConvention::Act360.split_years(d(2020, 1, 1), d(2020, 12, 31));
+    assert_eq!((years, rem), (0, 365.0 / 360.0));
+    let (years, rem) = DayCountConvention::Act360.split_years(d(2020, 1, 1), d(2021, 1, 1));
+    assert_eq!(years, 1);
+    assert!((rem - 6.0 / 360.0).abs() < 1e-12);
+    for conv in DayCountConvention::all() {
+        let (start, end) = (d(1978, 2, 28), d(2020, 5, 17));
+        let (years, rem) = conv.split_years(start, end);
+        assert_eq!(years, 42, "{conv}");
+        assert!(rem >= 0.0, "{conv}: {rem}");
+        assert!((years as f64 + rem - conv.yearfrac(start, end)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_nasd_methods_all() {
+    let delta = 1e-9;
+
+    let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    let end = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    let yfs = DayCountConvention::US30360.nasd_methods_all(start, end);
+    assert!((yfs[0] - DayCountConvention::US30360.yearfrac(start, end)).abs() < delta);
+    assert!((yfs[0] - 43.0 / 360.0).abs() < delta);
+    assert!((yfs[3] - 45.0 / 360.0).abs() < delta);
+    assert_eq!(
+        yfs,
+        DayCountConvention::US30360.nasd_methods_all(end, start)
+    );
+
+    let yfs = DayCountConvention::Act360.nasd_methods_all(start, end);
+    assert_eq!(yfs, [DayCountConvention::Act360.yearfrac(start, end); 4]);
+}
+
+#[test]
+fn test_yearfrac_tenor() {
+    let delta = 1e-9;
+
+    let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+    let yf = DayCountConvention::US30360
+        .yearfrac_tenor(start, "6M")
+        .unwrap();
+    assert!((yf - 0.5).abs() < delta);
+    let yf = DayCountConvention::Act365
+        .yearfrac_tenor(start, "1Y")
+        .unwrap();
+    assert!((yf - 1.0).abs() < delta);
+    let yf = DayCountConvention::Act360
+        .yearfrac_tenor(start, "90D")
+        .unwrap();
+    assert!((yf - 0.25).abs() < delta);
+    let yf = DayCountConvention::Act365
+        .yearfrac_tenor(start, "2W")
+        .unwrap();
+    assert!((yf - 14.0 / 365.0).abs() < delta);
+
+    assert!(matches!(
+        DayCountConvention::Act365.yearfrac_tenor(start, "bad"),
+        Err(DayCountConventionError::InvalidTenor { .. })
+    ));
+    assert!(DayCountConvention::Act365
+        .yearfrac_tenor(start, "M")
+        .is_err());
+    assert!(DayCountConvention::Act365
+        .yearfrac_tenor(start, "")
+        .is_err());
+}
+
+#[test]
+fn test_add_months() {
+    let dt = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+    assert_eq!(
+        add_months(dt, 1),
+        NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()
+    );
+    assert_eq!(
+        add_months(dt, 13),
+        NaiveDate::from_ymd_opt(2021, 2, 28).unwrap()
+    );
+    assert_eq!(
+        add_months(dt, -1),
+        NaiveDate::from_ymd_opt(2019, 12, 31).unwrap()
+    );
+    assert_eq!(add_months(dt, 0), dt);
+}
+
+#[test]
+fn test_round_to_period() {
+    let conv = DayCountConvention::Act365;
+    let anchor = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+
+    let date = NaiveDate::from_ymd_opt(2021, 6, 1).unwrap();
+    let snapped = conv.round_to_period(anchor, date, 3);
+    assert_eq!(snapped, NaiveDate::from_ymd_opt(2021, 7, 15).unwrap());
+
+    let date = NaiveDate::from_ymd_opt(2021, 5, 20).unwrap();
+    let snapped = conv.round_to_period(anchor, date, 3);
+    assert_eq!(snapped, NaiveDate::from_ymd_opt(2021, 4, 15).unwrap());
+
+    let date = NaiveDate::from_ymd_opt(2020, 9, 1).unwrap();
+    let snapped = conv.round_to_period(anchor, date, 6);
+    assert_eq!(snapped, NaiveDate::from_ymd_opt(2020, 7, 15).unwrap());
+
+    assert_eq!(conv.round_to_period(anchor, anchor, 6), anchor);
+    assert_eq!(conv.round_to_period(anchor, date, 0), date);
+}
+
+#[test]
+fn test_implied_rate() {
+    let delta = 1e-12;
+
+    let start = NaiveDate::from_ymd_opt(1993, 12, 2).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 4, 18).unwrap();
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+        DayCountConvention::EU30360,
+    ] {
+        let accrued = conv
+            .accrued_interest(start, end, 0.0425, 1_000_000.0)
+            .unwrap();
+        let rate = conv.implied_rate(start, end, 1_000_000.0, accrued).unwrap();
+        assert!((rate - 0.0425).abs() < delta);
+    }
+
+    let conv = DayCountConvention::Act365;
+    assert!(matches!(
+        conv.implied_rate(start, start, 100.0, 1.0),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.implied_rate(start, end, 0.0, 1.0),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn test_time_weighted_average() {
+    let delta = 1e-12;
+
+    let samples = [
+        (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 1.0),
+        (NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(), 2.0),
+        (NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(), 6.0),
+    ];
+    // Equal 90-day segments under 30/360: (1.5 + 4.0) / 2
+    let avg = DayCountConvention::US30360.time_weighted_average(&samples);
+    assert!((avg - 2.75).abs() < delta);
+
+    let flat = [
+        (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 0.03),
+        (NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(), 0.03),
+        (NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(), 0.03),
+    ];
+    let avg = DayCountConvention::ActAct.time_weighted_average(&flat);
+    assert!((avg - 0.03).abs() < delta);
+}
+
+#[test]
+#[should_panic]
+fn test_time_weighted_average_unsorted() {
+    let samples = [
+        (NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(), 1.0),
+        (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 2.0),
+    ];
+    DayCountConvention::Act365.time_weighted_average(&samples);
+}
+
+#[test]
+fn test_family() {
+    assert_eq!(
+        DayCountConvention::US30360.family(),
+        ConventionFamily::Thirty360
+    );
+    assert_eq!(
+        DayCountConvention::ActAct.family(),
+        ConventionFamily::Actual
+    );
+    assert_eq!(
+        DayCountConvention::Act360.family(),
+        ConventionFamily::Actual
+    );
+    assert_eq!(
+        DayCountConvention::Act365.family(),
+        ConventionFamily::Actual
+    );
+    assert_eq!(
+        DayCountConvention::EU30360.family(),
+        ConventionFamily::Thirty360
+    );
+}
+
+#[test]
+fn test_cumulative_fractions() {
+    let delta = 1e-12;
+
+    let anchor = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let dates = [
+        NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2021, 4, 11).unwrap(),
+        NaiveDate::from_ymd_opt(2021, 9, 28).unwrap(),
+        NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+    ];
+    let ts = DayCountConvention::Act365.cumulative_fractions(anchor, &dates);
+    assert_eq!(ts.len(), dates.len());
+    assert!(ts.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(ts[0], 0.0);
+    assert!((ts[1] - 100.0 / 365.0).abs() < delta);
+    assert!((ts[2] - 270.0 / 365.0).abs() < delta);
+    assert!((ts[3] - 730.0 / 365.0).abs() < delta);
+}
+
+#[test]
+fn test_leap_year_fraction() {
+    let delta = 1e-12;
+
+    let start = NaiveDate::from_ymd_opt(2019, 10, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 4, 1).unwrap();
+    let yf = DayCountConvention::Act360.leap_year_fraction(2020, start, end);
+    assert!((yf - 91.0 / 366.0).abs() < delta);
+    let yf = DayCountConvention::ActAct.leap_year_fraction(2020, end, start);
+    assert!((yf - 91.0 / 366.0).abs() < delta);
+    let yf = DayCountConvention::US30360.leap_year_fraction(2020, start, end);
+    assert!((yf - 0.25).abs() < delta);
+
+    let end = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+    let yf = DayCountConvention::Act365.leap_year_fraction(2020, start, end);
+    assert!((yf - 1.0).abs() < delta);
+    let yf = DayCountConvention::Act365.leap_year_fraction(2018, start, end);
+    assert_eq!(yf, 0.0);
+}
+
+#[test]
+fn test_from_ints() {
+    let convs = DayCountConvention::from_ints(&[0, 1, 2, 3, 4, 1]).unwrap();
+    assert_eq!(
+        convs,
+        vec![
+            DayCountConvention::US30360,
+            DayCountConvention::ActAct,
+            DayCountConvention::Act360,
+            DayCountConvention::Act365,
+            DayCountConvention::EU30360,
+            DayCountConvention::ActAct,
+        ]
+    );
+    assert!(DayCountConvention::from_ints(&[]).unwrap().is_empty());
+
+    let err = DayCountConvention::from_ints(&[0, 1, 8, 9]).unwrap_err();
+    assert!(matches!(
+        err,
+        DayCountConventionError::InvalidValueAt { index: 2, ref val } if val == "8"
+    ));
+}
+
+#[test]
+fn test_accrual_with_stub_convention() {
+    let delta = 1e-9;
+
+    let schedule = [
+        (d(2021, 2, 10), d(2021, 6, 15), true),
+        (d(2021, 6, 15), d(2021, 12, 15), false),
+        (d(2021, 12, 15), d(2022, 6, 15), false),
+    ];
+    let ai = DayCountConvention::accrual_with_stub_convention(
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        &schedule,
+        1_000.0,
+        0.04,
+    )
+    .unwrap();
+    let stub = 1_000.0 * 0.04 * 125.0 / 365.0;
+    assert!((ai - (stub + 20.0 + 20.0)).abs() < delta);
+
+    let same = DayCountConvention::accrual_with_stub_convention(
+        DayCountConvention::Act360,
+        DayCountConvention::Act360,
+        &schedule,
+        1_000.0,
+        0.04,
+    )
+    .unwrap();
+    let full = DayCountConvention::Act360
+        .accrued_interest(d(2021, 2, 10), d(2022, 6, 15), 0.04, 1_000.0)
+        .unwrap();
+    assert!((same - full).abs() < delta);
+}
+
+#[test]
+fn test_convention_delta() {
+    let delta = 1e-9;
+
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    let diff = DayCountConvention::convention_delta(
+        DayCountConvention::US30360,
+        DayCountConvention::Act365,
+        start,
+        end,
+    );
+    assert!((diff - (42.21388888889 - 42.24383561644)).abs() < delta);
+    let rev = DayCountConvention::convention_delta(
+        DayCountConvention::Act365,
+        DayCountConvention::US30360,
+        start,
+        end,
+    );
+    assert_eq!(diff, -rev);
+}
+
+#[test]
+fn test_yearfrac_scaled() {
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    let conv = DayCountConvention::US30360;
+    assert_eq!(conv.yearfrac_scaled(start, end, 6), 42_213_889);
+    assert_eq!(conv.yearfrac_scaled(start, end, 9), 42_213_888_889);
+    let conv = DayCountConvention::ActAct;
+    assert_eq!(conv.yearfrac_scaled(start, end, 6), 42_214_249);
+    assert_eq!(conv.yearfrac_scaled(start, end, 9), 42_214_249_331);
+    assert_eq!(conv.yearfrac_scaled(start, end, 0), 42);
+    assert_eq!(conv.yearfrac_scaled(start, start, 9), 0);
+    assert_eq!(conv.yearfrac_scaled(start, end, 19), i64::MAX);
+    assert_eq!(
+        conv.yearfrac_scaled(start, end, i32::MAX as u32 + 1),
+        i64::MAX
+    );
+    assert_eq!(conv.yearfrac_scaled(start, end, u32::MAX), i64::MAX);
+}
+
+#[test]
+fn test_validate_schedule() {
+    let semiannual = [
+        d(2020, 8, 31),
+        d(2021, 2, 28),
+        d(2021, 8, 31),
+        d(2022, 2, 28),
+    ];
+    assert!(validate_schedule(&semiannual, 6, 0).is_ok());
+    assert!(validate_schedule(&semiannual[..1], 6, 0).is_ok());
+
+    let gap = [d(2021, 1, 15), d(2021, 7, 15), d(2022, 7, 15)];
+    let err = validate_schedule(&gap, 6, 5).unwrap_err();
+    assert!(err.contains("2021-07-15"));
+    assert!(err.contains("2022-07-15"));
+
+    let duplicated = [d(2021, 1, 15), d(2021, 1, 15), d(2021, 7, 15)];
+    assert!(validate_schedule(&duplicated, 6, 5).is_err());
+}
+
+#[test]
+fn test_min_nonzero_fraction() {
+    assert_eq!(
+        DayCountConvention::Act360.min_nonzero_fraction(),
+        1.0 / 360.0
+    );
+    assert_eq!(
+        DayCountConvention::Act365.min_nonzero_fraction(),
+        1.0 / 365.0
+    );
+    assert_eq!(
+        DayCountConvention::ActAct.min_nonzero_fraction(),
+        1.0 / 366.0
+    );
+
+    let start = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 3, 2).unwrap();
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+        DayCountConvention::EU30360,
+    ] {
+        assert!(conv.yearfrac(start, end) >= conv.min_nonzero_fraction());
+    }
+}
+
+#[test]
+fn test_zero_coupon_price() {
+    let delta = 1e-9;
+
+    let settle = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let maturity = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    let conv = DayCountConvention::US30360;
+    let price = conv
+        .zero_coupon_price(settle, maturity, 100.0, 0.05, Compounding::Continuous)
+        .unwrap();
+    assert!((price - 77.88007830714).abs() < delta);
+    let price = conv
+        .zero_coupon_price(settle, maturity, 100.0, 0.05, Compounding::Periodic(1))
+        .unwrap();
+    assert!((price - 78.35261664684).abs() < delta);
+    let price = conv
+        .zero_coupon_price(settle, maturity, 100.0, 0.05, Compounding::Simple)
+        .unwrap();
+    assert!((price - 80.0).abs() < delta);
+
+    // 1826 days under Act/365
+    let conv = DayCountConvention::Act365;
+    let price = conv
+        .zero_coupon_price(settle, maturity, 100.0, 0.05, Compounding::Continuous)
+        .unwrap();
+    assert!((price - 100.0 * (-0.05 * 1826.0 / 365.0f64).exp()).abs() < delta);
+    let price = conv
+        .zero_coupon_price(settle, settle, 100.0, 0.05, Compounding::Periodic(1))
+        .unwrap();
+    assert_eq!(price, 100.0);
+}
+
+#[test]
+fn test_coupon_period_iter() {
+    let conv = DayCountConvention::Act365;
+
+    let periods: Vec<_> = conv
+        .coupon_period_iter(d(2020, 8, 31), d(2022, 1, 15), 6)
+        .collect();
+    let expected: Vec<_> = [
+        (d(2020, 8, 31), d(2021, 2, 28)),
+        (d(2021, 2, 28), d(2021, 8, 31)),
+        (d(2021, 8, 31), d(2022, 1, 15)),
+    ]
+    .into_iter()
+    .map(|(start, end)| (start, end, conv.yearfrac(start, end)))
+    .collect();
+    assert_eq!(periods, expected);
+
+    let total: f64 = periods.iter().map(|(_, _, yf)| yf).sum();
+    assert!((total - conv.yearfrac(d(2020, 8, 31), d(2022, 1, 15))).abs() < 1e-12);
+
+    assert_eq!(
+        conv.coupon_period_iter(d(2021, 1, 1), d(2021, 1, 1), 6)
+            .count(),
+        0
+    );
+    assert_eq!(
+        conv.coupon_period_iter(d(2021, 1, 1), d(2022, 1, 1), 0)
+            .count(),
+        0
+    );
+    assert_eq!(
+        conv.coupon_period_iter(d(2021, 1, 1), d(2051, 1, 1), 1)
+            .count(),
+        360
+    );
+}
+
+#[test]
+fn test_yearfrac_thirty360_sia() {
+    let delta = 1e-12;
+    // Feb-28 start, 31st end: Excel US moves the start to 30 but keeps the 31st
+    let (start, end) = (d(2021, 2, 28), d(2021, 3, 31));
+    let us = DayCountConvention::yearfrac_thirty360(start, end, Thirty360Preset::Us);
+    let sia = DayCountConvention::yearfrac_thirty360(start, end, Thirty360Preset::Sia);
+    assert!((us - DayCountConvention::US30360.yearfrac(start, end)).abs() < delta);
+    assert!((us - 31.0 / 360.0).abs() < delta);
+    assert!((sia - 33.0 / 360.0).abs() < delta);
+
+    // 31st start and end: both adjust
+    let (start, end) = (d(2021, 1, 31), d(2021, 3, 31));
+    let sia = DayCountConvention::yearfrac_thirty360(end, start, Thirty360Preset::Sia);
+    assert!((sia - 60.0 / 360.0).abs() < delta);
+
+    let eu = DayCountConvention::yearfrac_thirty360(start, end, Thirty360Preset::European);
+    assert_eq!(eu, DayCountConvention::EU30360.yearfrac(start, end));
+}
+
+#[test]
+fn test_portfolio_accrual() {
+    let delta = 1e-9;
+    let positions = [
+        (d(2021, 1, 1), d(2021, 4, 11), 1_000_000.0, 0.0365),
+        (d(2021, 3, 1), d(2021, 3, 31), 500_000.0, 0.073),
+        (d(2020, 12, 1), d(2021, 12, 1), -250_000.0, 0.02),
+    ];
+    // 100, 30 and 365 days under Act/365
+    let expected =
+        1_000_000.0 * 0.0365 * 100.0 / 365.0 + 500_000.0 * 0.073 * 30.0 / 365.0 - 250_000.0 * 0.02;
+    let total = DayCountConvention::Act365
+        .portfolio_accrual(&positions)
+        .unwrap();
+    assert!((total - expected).abs() < delta);
+    assert!((total - 8_000.0).abs() < delta);
+    assert_eq!(
+        DayCountConvention::Act365.portfolio_accrual(&[]).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn test_non_finite_inputs() {
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    let conv = DayCountConvention::Act365;
+
+    for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(
+            conv.accrued_interest(start, end, bad, 100.0),
+            Err(DayCountConventionError::NonFiniteInput {
+                name: "coupon_rate",
+                ..
+            })
+        ));
+        assert!(matches!(
+            conv.accrued_interest(start, end, 0.05, bad),
+            Err(DayCountConventionError::NonFiniteInput { name: "face", .. })
+        ));
+        assert!(matches!(
+            conv.implied_rate(start, end, bad, 1.0),
+            Err(DayCountConventionError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            conv.portfolio_accrual(&[(start, end, 100.0, 0.01), (start, end, bad, 0.01)]),
+            Err(DayCountConventionError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            conv.zero_coupon_price(start, end, 100.0, bad, Compounding::Continuous),
+            Err(DayCountConventionError::NonFiniteInput {
+                name: "yield_rate",
+                ..
+            })
+        ));
+    }
+    assert!(matches!(
+        conv.zero_coupon_price(start, end, 100.0, 0.05, Compounding::Periodic(0)),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn test_yearfrac_clamped() {
+    let conv = DayCountConvention::US30360;
+    let start = d(2021, 1, 1);
+    assert_eq!(conv.yearfrac_clamped(start, d(2021, 2, 1), 0.25, 1.0), 0.25);
+    assert_eq!(conv.yearfrac_clamped(start, d(2021, 7, 1), 0.25, 1.0), 0.5);
+    assert_eq!(conv.yearfrac_clamped(start, d(2024, 1, 1), 0.25, 1.0), 1.0);
+    assert_eq!(conv.yearfrac_clamped(start, d(2021, 7, 1), 0.5, 0.5), 0.5);
+}
+
+#[test]
+#[should_panic]
+fn test_yearfrac_clamped_bad_bounds() {
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+    DayCountConvention::US30360.yearfrac_clamped(start, end, 1.0, 0.25);
+}
+
+#[test]
+fn test_relative_fraction() {
+    let delta = 1e-12;
+    let conv = DayCountConvention::US30360;
+    let (start, end) = (d(2021, 1, 1), d(2022, 1, 1));
+
+    assert_eq!(conv.relative_fraction(start, start, end, false), 0.0);
+    assert!((conv.relative_fraction(d(2021, 7, 1), start, end, false) - 0.5).abs() < delta);
+    assert_eq!(conv.relative_fraction(end, start, end, false), 1.0);
+
+    let after = d(2022, 7, 1);
+    assert_eq!(conv.relative_fraction(after, start, end, false), 1.0);
+    assert!((conv.relative_fraction(after, start, end, true) - 1.5).abs() < delta);
+    let before = d(2020, 10, 1);
+    assert_eq!(conv.relative_fraction(before, start, end, false), 0.0);
+    assert!((conv.relative_fraction(before, start, end, true) + 0.25).abs() < delta);
+
+    assert_eq!(conv.relative_fraction(before, start, start, true), 0.0);
+    assert_eq!(conv.relative_fraction(after, start, start, true), 1.0);
+}
+
+#[test]
+fn test_effective_year_length() {
+    let conv = DayCountConvention::ActAct;
+
+    // same year
+    assert_eq!(
+        conv.effective_year_length(d(2020, 2, 1), d(2020, 11, 1)),
+        366.0
+    );
+    assert_eq!(
+        conv.effective_year_length(d(2021, 2, 1), d(2021, 11, 1)),
+        365.0
+    );
+    // adjacent years, up to one year apart
+    assert_eq!(
+        conv.effective_year_length(d(2019, 7, 1), d(2020, 3, 1)),
+        366.0
+    );
+    assert_eq!(
+        conv.effective_year_length(d(2020, 3, 1), d(2019, 7, 1)),
+        366.0
+    );
+    assert_eq!(
+        conv.effective_year_length(d(2020, 7, 1), d(2021, 3, 1)),
+        365.0
+    );
+    // multi year
+    let yl = conv.effective_year_length(d(2019, 1, 1), d(2021, 6, 1));
+    assert!((yl - 1096.0 / 3.0).abs() < 1e-12);
+
+    let (start, end) = (d(2019, 7, 1), d(2021, 6, 1));
+    let days = (end - start).num_days() as f64;
+    assert!(
+        (days / conv.effective_year_length(start, end) - conv.yearfrac(start, end)).abs() < 1e-15
+    );
+
+    assert_eq!(
+        DayCountConvention::Act365.effective_year_length(start, end),
+        365.0
+    );
+    assert_eq!(
+        DayCountConvention::US30360.effective_year_length(start, end),
+        360.0
+    );
+}
+
+#[test]
+fn test_min_max_convention() {
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    assert_eq!(
+        DayCountConvention::min_convention(start, end),
+        DayCountConvention::US30360
+    );
+    assert_eq!(
+        DayCountConvention::max_convention(start, end),
+        DayCountConvention::Act360
+    );
+    assert_eq!(
+        DayCountConvention::min_convention(end, start),
+        DayCountConvention::US30360
+    );
+
+    // all conventions agree on 0
+    assert_eq!(
+        DayCountConvention::min_convention(start, start),
+        DayCountConvention::US30360
+    );
+    assert_eq!(
+        DayCountConvention::max_convention(start, start),
+        DayCountConvention::US30360
+    );
+}
+
+#[test]
+fn test_act365_fixed_alias() {
+    let start = NaiveDate::from_ymd_opt(1993, 12, 2).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 4, 18).unwrap();
+    for alias in ["act/365f", "actual/365 fixed"] {
+        let conv = DayCountConvention::from_str(alias).unwrap();
+        assert_eq!(conv, DayCountConvention::Act365);
+        assert_eq!(
+            conv.yearfrac(start, end),
+            DayCountConvention::Act365.yearfrac(start, end)
+        );
+    }
+}
+
+#[test]
+fn test_forward_yearfrac() {
+    let (valuation, forward_start, forward_end) = (d(2021, 3, 15), d(2022, 3, 15), d(2024, 3, 15));
+
+    let (to_start, length) =
+        DayCountConvention::US30360.forward_yearfrac(valuation, forward_start, forward_end);
+    assert_eq!((to_start, length), (1.0, 2.0));
+
+    let (to_start, length) =
+        DayCountConvention::Act365.forward_yearfrac(valuation, forward_start, forward_end);
+    assert_eq!(to_start, 1.0);
+    assert_eq!(length, 731.0 / 365.0);
+
+    let (to_start, length) =
+        DayCountConvention::Act365.forward_yearfrac(valuation, valuation, valuation);
+    assert_eq!((to_start, length), (0.0, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn test_forward_yearfrac_started() {
+    DayCountConvention::Act365.forward_yearfrac(d(2022, 6, 1), d(2022, 3, 15), d(2024, 3, 15));
+}
+
+#[test]
+fn test_fraction_by_weekday_class() {
+    let delta = 1e-12;
+    // Thu 2021-12-23 .. Mon 2022-01-03: Christmas on a Saturday, Boxing day observed Mon 27th
+    let holidays = vec![d(2021, 12, 25), d(2021, 12, 27), d(2022, 1, 1)];
+    assert!(holidays.is_holiday(d(2021, 12, 27)));
+    let (start, end) = (d(2021, 12, 23), d(2022, 1, 3));
+    let conv = DayCountConvention::Act365;
+    let (business, weekend, holiday) = conv.fraction_by_weekday_class(start, end, &holidays);
+    assert!((business - 6.0 / 365.0).abs() < delta);
+    assert!((weekend - 4.0 / 365.0).abs() < delta);
+    assert!((holiday - 1.0 / 365.0).abs() < delta);
+    assert!((business + weekend + holiday - conv.yearfrac(start, end)).abs() < delta);
+
+    let (business, weekend, holiday) = conv.fraction_by_weekday_class(end, start, &WeekendsOnly);
+    assert!((business - 7.0 / 365.0).abs() < delta);
+    assert!((weekend - 4.0 / 365.0).abs() < delta);
+    assert_eq!(holiday, 0.0);
+}
+
+#[test]
+fn test_annualization_sqrt_factor() {
+    let delta = 1e-12;
+    let (start, end) = (d(2021, 3, 1), d(2021, 3, 2));
+    let factor = DayCountConvention::Act360.annualization_sqrt_factor(start, end);
+    assert!((factor - 360f64.sqrt()).abs() < delta);
+    let factor = DayCountConvention::ActAct.annualization_sqrt_factor(start, end);
+    assert!((factor - 365f64.sqrt()).abs() < delta);
+    // weekly sampling
+    let factor = DayCountConvention::Act360.annualization_sqrt_factor(start, d(2021, 3, 8));
+    assert!((factor - (360.0f64 / 7.0).sqrt()).abs() < delta);
+}
+
+#[test]
+#[should_panic]
+fn test_annualization_sqrt_factor_zero() {
+    let start = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+    DayCountConvention::Act360.annualization_sqrt_factor(start, start);
+}
+
+#[test]
+fn test_yearfrac_accounting_360() {
+    let delta = 1e-12;
+    let acc = DayCountConvention::yearfrac_accounting_360;
+    let us = |start, end| DayCountConvention::US30360.yearfrac(start, end);
+
+    // same as US where no adjustment applies
+    let (start, end) = (d(1993, 12, 2), d(2022, 4, 18));
+    assert!((acc(start, end) - us(start, end)).abs() < delta);
+    assert!((acc(end, start) - 28.37777777778).abs() < 1e-9);
+
+    // end of February start: US moves it to the 30th
+    let (start, end) = (d(2021, 2, 28), d(2021, 3, 31));
+    assert!((acc(start, end) - 33.0 / 360.0).abs() < delta);
+    assert!((us(start, end) - 31.0 / 360.0).abs() < delta);
+
+    // 30th to 31st: US treats both as the 30th
+    let (start, end) = (d(2021, 1, 30), d(2021, 1, 31));
+    assert!((acc(start, end) - 1.0 / 360.0).abs() < delta);
+    assert_eq!(us(start, end), 0.0);
+
+    // 31st to the 1st: no adjustment at all
+    assert_eq!(acc(d(2021, 1, 31), d(2021, 2, 1)), 0.0);
+    assert!((us(d(2021, 1, 31), d(2021, 2, 1)) - 1.0 / 360.0).abs() < delta);
+}
+
+#[test]
+fn test_require_business_days() {
+    let holidays = vec![d(2021, 12, 27)];
+
+    assert!(
+        DayCountConvention::require_business_days(d(2021, 12, 23), d(2021, 12, 28), &holidays)
+            .is_ok()
+    );
+    let err =
+        DayCountConvention::require_business_days(d(2021, 12, 23), d(2021, 12, 26), &holidays)
+            .unwrap_err();
+    assert!(
+        matches!(err, DayCountConventionError::NonBusinessDay { date } if date == d(2021, 12, 26))
+    );
+    let err =
+        DayCountConvention::require_business_days(d(2021, 12, 27), d(2021, 12, 28), &holidays)
+            .unwrap_err();
+    assert!(
+        matches!(err, DayCountConventionError::NonBusinessDay { date } if date == d(2021, 12, 27))
+    );
+    assert!(DayCountConvention::require_business_days(
+        d(2021, 12, 27),
+        d(2021, 12, 28),
+        &WeekendsOnly
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_macaulay_duration() {
+    // 2y 6% semiannual bond priced at par
+    let settle = d(2021, 1, 15);
+    let cashflows = [
+        (d(2021, 7, 15), 3.0),
+        (d(2022, 1, 15), 3.0),
+        (d(2022, 7, 15), 3.0),
+        (d(2023, 1, 15), 103.0),
+    ];
+    let conv = DayCountConvention::US30360;
+    let duration = conv
+        .macaulay_duration(settle, &cashflows, 0.06, Compounding::Periodic(2))
+        .unwrap();
+    assert!((duration - 1.9143056774473).abs() < 1e-9);
+
+    // past cashflows are ignored
+    let mut with_past = cashflows.to_vec();
+    with_past.insert(0, (d(2020, 7, 15), 3.0));
+    let same = conv
+        .macaulay_duration(settle, &with_past, 0.06, Compounding::Periodic(2))
+        .unwrap();
+    assert_eq!(same, duration);
+
+    assert!(matches!(
+        conv.macaulay_duration(settle, &[], 0.06, Compounding::Periodic(2)),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.macaulay_duration(settle, &cashflows, f64::NAN, Compounding::Continuous),
+        Err(DayCountConventionError::NonFiniteInput {
+            name: "yield_rate",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_labeled_days() {
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    let expected = [
+        (DayCountConvention::US30360, "nasd30/360"),
+        (DayCountConvention::ActAct, "act/act"),
+        (DayCountConvention::Act360, "act360"),
+        (DayCountConvention::Act365, "act365"),
+        (DayCountConvention::EU30360, "eur30/360"),
+    ];
+    for (conv, label) in expected {
+        assert_eq!(conv.labeled_days(start, end), (label.to_owned(), 15419));
+        assert_eq!(DayCountConvention::from_str(label).unwrap(), conv);
+    }
+    assert_eq!(
+        DayCountConvention::Act365.labeled_days(end, start),
+        ("act365".to_owned(), -15419)
+    );
+}
+
+#[test]
+fn test_integrate() {
+    let delta = 1e-9;
+    let (start, end) = (d(2021, 1, 1), d(2022, 1, 1));
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::Act365,
+        DayCountConvention::EU30360,
+    ] {
+        assert!((conv.integrate(start, end, |_| 1.0) - 1.0).abs() < delta);
+    }
+    let area = DayCountConvention::Act360.integrate(start, end, |_| 1.0);
+    assert!((area - 365.0 / 360.0).abs() < delta);
+
+    // leap year under Act/Act
+    let area = DayCountConvention::ActAct.integrate(d(2020, 1, 1), d(2020, 12, 31), |_| 1.0);
+    assert!((area - 365.0 / 366.0).abs() < delta);
+
+    // a step function: 1% in H1, 3% in H2
+    let mid = d(2021, 7, 1);
+    let area =
+        DayCountConvention::US30360.integrate(
+            start,
+            end,
+            |day| {
+                if day < mid {
+                    0.01
+                } else {
+                    0.03
+                }
+            },
+        );
+    assert!((area - (181.0 * 0.01 + 184.0 * 0.03) / 365.0).abs() < delta);
+    assert_eq!(
+        DayCountConvention::Act365.integrate(start, start, |_| 1.0),
+        0.0
+    );
+}
+
+#[test]
+fn test_round_to_days() {
+    let pairs = [
+        (d(1978, 2, 28), d(2020, 5, 17)),
+        (d(1993, 12, 2), d(2022, 4, 18)),
+        (d(2020, 2, 29), d(2021, 3, 31)),
+    ];
+    for (start, end) in pairs {
+        let days = (end - start).num_days();
+        for conv in [
+            DayCountConvention::ActAct,
+            DayCountConvention::Act360,
+            DayCountConvention::Act365,
+        ] {
+            assert_eq!(conv.round_to_days(start, end), days);
+            assert_eq!(conv.round_to_days(end, start), days);
+        }
+    }
+
+    // 1978-02-28 is the end of February, so US moves it to the 30th
+    let (start, end) = pairs[0];
+    assert_eq!(DayCountConvention::US30360.round_to_days(start, end), 15197);
+    assert_eq!(DayCountConvention::EU30360.round_to_days(start, end), 15199);
+    let (start, end) = (d(2021, 1, 30), d(2021, 1, 31));
+    assert_eq!(DayCountConvention::US30360.round_to_days(start, end), 0);
+    assert_eq!(DayCountConvention::Act360.round_to_days(start, end), 1);
+}
+
+#[test]
+fn test_notional_weighted_fraction() {
+    let delta = 1e-12;
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+
+    let yf = DayCountConvention::notional_weighted_fraction(
+        start,
+        end,
+        &[
+            (DayCountConvention::Act360, 7_000_000.0),
+            (DayCountConvention::Act365, 3_000_000.0),
+        ],
+    )
+    .unwrap();
+    assert!((yf - (0.7 * 365.0 / 360.0 + 0.3)).abs() < delta);
+
+    assert!(matches!(
+        DayCountConvention::notional_weighted_fraction(start, end, &[]),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        DayCountConvention::notional_weighted_fraction(
+            start,
+            end,
+            &[
+                (DayCountConvention::Act360, 1.0),
+                (DayCountConvention::Act365, -1.0)
+            ]
+        ),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        DayCountConvention::notional_weighted_fraction(
+            start,
+            end,
+            &[(DayCountConvention::Act360, f64::NAN)]
+        ),
+        Err(DayCountConventionError::NonFiniteInput { .. })
+    ));
+}
+
+#[test]
+fn test_is_convention_sensitive() {
+    assert!(DayCountConvention::is_convention_sensitive(
+        d(1978, 2, 28),
+        d(2020, 5, 17)
+    ));
+    assert!(DayCountConvention::is_convention_sensitive(
+        d(2021, 3, 1),
+        d(2021, 3, 2)
+    ));
+    assert!(!DayCountConvention::is_convention_sensitive(
+        d(2021, 3, 1),
+        d(2021, 3, 1)
+    ));
+}
+
+#[test]
+fn test_split_accrual() {
+    let delta = 1e-9;
+    let (start, handover, end) = (d(2021, 1, 15), d(2021, 1, 31), d(2021, 2, 15));
+
+    for conv in [DayCountConvention::Act360, DayCountConvention::Act365] {
+        let (seller, buyer) = conv
+            .split_accrual(start, handover, end, 1_000_000.0, 0.05)
+            .unwrap();
+        let full = conv
+            .accrued_interest(start, end, 0.05, 1_000_000.0)
+            .unwrap();
+        assert!((seller + buyer - full).abs() < delta);
+    }
+
+    // US 30/360 is not additive around the 31st
+    let conv = DayCountConvention::US30360;
+    let (seller, buyer) = conv
+        .split_accrual(start, handover, end, 360.0, 1.0)
+        .unwrap();
+    assert!((seller - 16.0).abs() < delta);
+    assert!((buyer - 15.0).abs() < delta);
+    let full = conv.accrued_interest(start, end, 1.0, 360.0).unwrap();
+    assert!((full - 30.0).abs() < delta);
+
+    assert!(matches!(
+        conv.split_accrual(start, d(2021, 3, 1), end, 100.0, 0.05),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.split_accrual(start, handover, end, 100.0, f64::NAN),
+        Err(DayCountConventionError::NonFiniteInput { .. })
+    ));
+}
+
+#[test]
+fn test_quarterly_fractions() {
+    let delta = 1e-12;
+    let (start, end) = (d(2021, 2, 15), d(2021, 5, 10));
+    let conv = DayCountConvention::Act360;
+    let parts = conv.quarterly_fractions(start, end);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].0, (2021, 1));
+    assert_eq!(parts[1].0, (2021, 2));
+    assert!((parts[0].1 - 45.0 / 360.0).abs() < delta);
+    assert!((parts[1].1 - 39.0 / 360.0).abs() < delta);
+    let total: f64 = parts.iter().map(|(_, yf)| yf).sum();
+    assert!((total - conv.yearfrac(start, end)).abs() < delta);
+
+    let parts = conv.quarterly_fractions(d(2021, 11, 15), d(2022, 1, 1));
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].0, (2021, 4));
+    let parts = conv.quarterly_fractions(d(2022, 1, 10), d(2021, 11, 15));
+    assert_eq!(
+        parts.iter().map(|(q, _)| *q).collect::<Vec<_>>(),
+        vec![(2021, 4), (2022, 1)]
+    );
+    assert!(conv.quarterly_fractions(start, start).is_empty());
+}
+
+#[test]
+fn test_yield_to_maturity() {
+    let conv = DayCountConvention::US30360;
+    let settle = d(2020, 1, 15);
+
+    // a semi-annual 4% bond priced at par yields its coupon
+    let mut cashflows: Vec<(NaiveDate, f64)> =
+        (1..=10).map(|k| (add_months(settle, 6 * k), 2.0)).collect();
+    cashflows.last_mut().unwrap().1 += 100.0;
+    let ytm = conv
+        .yield_to_maturity(settle, 100.0, &cashflows, Compounding::Periodic(2))
+        .unwrap();
+    assert!((ytm - 0.04).abs() < 1e-10);
+
+    // round trip through the price at a known continuous yield
+    let price: f64 = cashflows
+        .iter()
+        .map(|(date, amount)| {
+            amount
+                * conv
+                    .discount_factor(settle, *date, 0.031, Compounding::Continuous)
+                    .unwrap()
+        })
+        .sum();
+    let ytm = conv
+        .yield_to_maturity(settle, price, &cashflows, Compounding::Continuous)
+        .unwrap();
+    assert!((ytm - 0.031).abs() < 1e-10);
+
+    assert!(matches!(
+        conv.yield_to_maturity(settle, 100.0, &cashflows[..0], Compounding::Simple),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.yield_to_maturity(settle, f64::NAN, &cashflows, Compounding::Simple),
+        Err(DayCountConventionError::NonFiniteInput { name: "price", .. })
+    ));
+    assert!(matches!(
+        conv.yield_to_maturity(settle, -50.0, &cashflows, Compounding::Continuous),
+        Err(DayCountConventionError::NoConvergence { .. })
+    ));
+}
+
+#[test]
+fn test_byte_round_trip() {
+    for b in 0..=u8::MAX {
+        match DayCountConvention::from_byte(b) {
+            Ok(conv) => assert_eq!(conv.to_byte(), b),
+            Err(err) => {
+                assert!(b > 7);
+                assert!(
+                    matches!(err, DayCountConventionError::InvalidValue { ref val } if *val == b.to_string())
+                );
+            }
+        }
+    }
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+        DayCountConvention::EU30360,
+        DayCountConvention::ActActISDA,
+        DayCountConvention::Bus252,
+    ] {
+        assert_eq!(DayCountConvention::from_byte(conv.to_byte()).unwrap(), conv);
+    }
+}
+
+#[test]
+fn test_yearfrac_traced() {
+    let us = DayCountConvention::US30360;
+    let eu = DayCountConvention::EU30360;
+
+    // end on the 31st only rolls once the start is on the 30th or 31st
+    let (yf, trace) = us.yearfrac_traced(d(2021, 1, 31), d(2021, 3, 31));
+    assert_eq!(yf, us.yearfrac(d(2021, 1, 31), d(2021, 3, 31)));
+    assert_eq!(trace, vec!["end_31_to_30", "start_31_to_30"]);
+    let (_, trace) = us.yearfrac_traced(d(2021, 1, 15), d(2021, 3, 31));
+    assert!(trace.is_empty());
+    let (_, trace) = eu.yearfrac_traced(d(2021, 1, 15), d(2021, 3, 31));
+    assert_eq!(trace, vec!["end_31_to_30"]);
+
+    // February end of month on both sides
+    let (yf, trace) = us.yearfrac_traced(d(2020, 2, 29), d(2021, 2, 28));
+    assert_eq!(yf, 1.0);
+    assert_eq!(trace, vec!["end_feb_eom_to_30", "start_feb_eom_to_30"]);
+    let (_, trace) = us.yearfrac_traced(d(2021, 2, 28), d(2020, 2, 29));
+    assert_eq!(trace, vec!["end_feb_eom_to_30", "start_feb_eom_to_30"]);
+    let (_, trace) = eu.yearfrac_traced(d(2020, 2, 29), d(2021, 2, 28));
+    assert!(trace.is_empty());
+
+    for conv in [
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+    ] {
+        let (yf, trace) = conv.yearfrac_traced(d(2021, 1, 31), d(2021, 3, 31));
+        assert_eq!(yf, conv.yearfrac(d(2021, 1, 31), d(2021, 3, 31)));
+        assert!(trace.is_empty());
+    }
+
+    // equal dates are not short-circuited
+    let (yf, trace) = us.yearfrac_traced(d(2021, 1, 31), d(2021, 1, 31));
+    assert_eq!(yf, 0.0);
+    assert_eq!(trace, vec!["end_31_to_30", "start_31_to_30"]);
+    let (yf, trace) = eu.yearfrac_traced(d(2021, 3, 15), d(2021, 3, 15));
+    assert_eq!(yf, 0.0);
+    assert!(trace.is_empty());
+}
+
+#[test]
+fn test_time_to_maturity() {
+    let conv = DayCountConvention::US30360;
+    let valuation = d(2021, 6, 15);
+
+    assert_eq!(conv.time_to_maturity(valuation, d(2020, 6, 15), 30.0), 0.0);
+    assert_eq!(conv.time_to_maturity(valuation, valuation, 30.0), 0.0);
+    assert_eq!(conv.time_to_maturity(valuation, d(2031, 6, 15), 30.0), 10.0);
+    assert_eq!(conv.time_to_maturity(valuation, d(2099, 6, 15), 30.0), 30.0);
+}
+
+#[test]
+#[should_panic]
+fn test_time_to_maturity_negative_cap() {
+    let d = NaiveDate::from_ymd_opt(2021, 6, 15).unwrap();
+    DayCountConvention::US30360.time_to_maturity(d, d, -1.0);
+}
+
+#[test]
+fn test_build_grid() {
+    let conv = DayCountConvention::ActAct;
+    let (anchor, start, end) = (d(2019, 7, 1), d(2020, 1, 1), d(2020, 12, 31));
+
+    let grid = conv.build_grid(anchor, start, end);
+    assert_eq!(grid.len() as i64, (end - start).num_days() + 1);
+    assert_eq!(grid[0], (start, conv.yearfrac(anchor, start)));
+    assert_eq!(grid[grid.len() - 1], (end, conv.yearfrac(anchor, end)));
+    assert!(grid
+        .windows(2)
+        .all(|w| w[1].0 == w[0].0.succ_opt().unwrap()));
+
+    assert_eq!(conv.build_grid(anchor, start, start).len(), 1);
+    assert!(conv.build_grid(anchor, end, start).is_empty());
+}
+
+#[test]
+fn test_yearfrac_30_365() {
+    let delta = 1e-12;
+    // Feb EOM to Feb EOM: 360 adjusted days, 365 actual days
+    let (start, end) = (d(2020, 2, 29), d(2021, 2, 28));
+    let yf = DayCountConvention::yearfrac_30_365(start, end);
+    assert!((yf - 360.0 / 365.0).abs() < delta);
+    assert_eq!(DayCountConvention::US30360.yearfrac(start, end), 1.0);
+    assert_eq!(DayCountConvention::Act365.yearfrac(start, end), 1.0);
+
+    // 31st handling: 59 actual days, 60 adjusted
+    let (start, end) = (d(2021, 1, 31), d(2021, 3, 31));
+    let yf = DayCountConvention::yearfrac_30_365(start, end);
+    assert!((yf - 60.0 / 365.0).abs() < delta);
+    assert!((DayCountConvention::US30360.yearfrac(start, end) - 60.0 / 360.0).abs() < delta);
+    assert!((DayCountConvention::Act365.yearfrac(start, end) - 59.0 / 365.0).abs() < delta);
+
+    assert_eq!(DayCountConvention::yearfrac_30_365(end, start), yf);
+}
+
+#[test]
+fn test_accrual_with_ex_div() {
+    let delta = 1e-9;
+    let d = |m, d| NaiveDate::from_ymd_opt(2021, m, d).unwrap();
+    let conv = DayCountConvention::Act365;
+    let (last, next, ex_div) = (d(1, 1), d(7, 1), d(6, 24));
+
+    // before ex-div: normal accrual
+    let ai = conv
+        .accrual_with_ex_div(last, d(6, 23), next, ex_div, 1000.0, 0.0365)
+        .unwrap();
+    assert!((ai - 173.0 * 0.1).abs() < delta);
+
+    // on and after ex-div: minus the interest up to the next coupon
+    let ai = conv
+        .accrual_with_ex_div(last, ex_div, next, ex_div, 1000.0, 0.0365)
+        .unwrap();
+    assert!((ai + 7.0 * 0.1).abs() < delta);
+    let ai = conv
+        .accrual_with_ex_div(last, d(6, 28), next, ex_div, 1000.0, 0.0365)
+        .unwrap();
+    assert!((ai + 3.0 * 0.1).abs() < delta);
+
+    assert!(matches!(
+        conv.accrual_with_ex_div(last, d(7, 2), next, ex_div, 1000.0, 0.0365),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.accrual_with_ex_div(last, d(6, 1), next, d(7, 5), 1000.0, 0.0365),
+        Err(DayCountConventionError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        conv.accrual_with_ex_div(last, d(6, 1), next, ex_div, 1000.0, f64::NAN),
+        Err(DayCountConventionError::NonFiniteInput { .. })
+    ));
+}
+
+#[test]
+fn test_act_act_full_year_boundaries() {
+    let conv = DayCountConvention::ActAct;
+
+    // exactly one year, in line with Excel's YEARFRAC(.., 1)
+    assert_eq!(conv.yearfrac(d(2020, 1, 1), d(2021, 1, 1)), 1.0);
+    assert_eq!(conv.yearfrac(d(2019, 1, 1), d(2020, 1, 1)), 1.0);
+    assert_eq!(conv.yearfrac(d(2020, 3, 1), d(2021, 3, 1)), 1.0);
+    assert_eq!(conv.yearfrac(d(2019, 6, 15), d(2020, 6, 15)), 1.0);
+
+    // the 366th day of a leap year is still inside it
+    assert_eq!(conv.yearfrac(d(2020, 1, 1), d(2020, 12, 31)), 365.0 / 366.0);
+    assert_eq!(conv.yearfrac(d(2021, 1, 1), d(2021, 12, 31)), 364.0 / 365.0);
+
+    // one day over a year falls back to averaging the years touched
+    let yf = conv.yearfrac(d(2020, 1, 1), d(2021, 1, 2));
+    assert!((yf - 367.0 / 365.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_progress() {
+    let conv = DayCountConvention::Act360;
+    let (start, end) = (d(2021, 1, 1), d(2021, 1, 31));
+
+    assert_eq!(conv.progress(start, start, end), 0.0);
+    assert_eq!(conv.progress(start, d(2020, 12, 1), end), 0.0);
+    assert_eq!(conv.progress(start, d(2021, 1, 16), end), 0.5);
+    assert_eq!(conv.progress(start, end, end), 1.0);
+    assert_eq!(conv.progress(start, d(2021, 3, 1), end), 1.0);
+}
+
+#[test]
+fn test_excess_over_whole_years() {
+    let conv = DayCountConvention::US30360;
+    let (start, end) = (d(1978, 2, 28), d(2020, 5, 17));
+
+    let excess = conv.excess_over_whole_years(start, end);
+    assert!((excess - 0.214).abs() < 1e-3);
+    assert!((excess + 42.0 - conv.yearfrac(start, end)).abs() < 1e-12);
+    assert_eq!(conv.excess_over_whole_years(end, start), excess);
+
+    let excess = conv.excess_over_whole_years(d(2020, 12, 1), d(2021, 1, 1));
+    assert!((excess - (30.0 / 360.0 - 1.0)).abs() < 1e-12);
+}
+
+#[test]
+fn test_yearfrac_intervals() {
+    let conv = DayCountConvention::Act360;
+
+    let disjoint = [
+        (d(2021, 1, 1), d(2021, 1, 11)),
+        (d(2021, 2, 1), d(2021, 2, 21)),
+    ];
+    assert_eq!(conv.yearfrac_intervals(&disjoint, false), 30.0 / 360.0);
+    assert_eq!(conv.yearfrac_intervals(&disjoint, true), 30.0 / 360.0);
+
+    // Jan 1 - Jan 21 and Jan 11 - Jan 31 share 10 days, the last one is reversed
+    let overlapping = [
+        (d(2021, 1, 11), d(2021, 1, 31)),
+        (d(2021, 1, 1), d(2021, 1, 21)),
+        (d(2021, 3, 11), d(2021, 3, 1)),
+        (d(2021, 1, 31), d(2021, 2, 5)),
+    ];
+    assert_eq!(conv.yearfrac_intervals(&overlapping, false), 55.0 / 360.0);
+    assert_eq!(conv.yearfrac_intervals(&overlapping, true), 45.0 / 360.0);
+
+    assert_eq!(conv.yearfrac_intervals(&[], true), 0.0);
+}
+
+#[test]
+fn test_act_act_isda() {
+    let delta = 1e-12;
+    let isda = DayCountConvention::ActActISDA;
+    assert_eq!(DayCountConvention::from_int(5).unwrap(), isda);
+    assert_eq!(DayCountConvention::from_str("act/act/isda").unwrap(), isda);
+    assert_eq!(isda.to_string(), "act/act/isda");
+
+    // 17 days of 2019 and 166 days of 2020
+    let (start, end) = (d(2019, 12, 15), d(2020, 6, 15));
+    let yf = isda.yearfrac(start, end);
+    assert!((yf - (17.0 / 365.0 + 166.0 / 366.0)).abs() < delta);
+    assert!((DayCountConvention::ActAct.yearfrac(start, end) - 183.0 / 366.0).abs() < delta);
+    assert_eq!(isda.yearfrac(end, start), yf);
+
+    // within one leap year
+    let yf = isda.yearfrac(d(2020, 2, 1), d(2020, 11, 1));
+    assert!((yf - 274.0 / 366.0).abs() < delta);
+    assert_eq!(isda.yearfrac(d(2020, 1, 1), d(2021, 1, 1)), 1.0);
+
+    // several year boundaries
+    let yf = isda.yearfrac(d(2019, 7, 1), d(2022, 3, 1));
+    assert!((yf - (184.0 / 365.0 + 2.0 + 59.0 / 365.0)).abs() < delta);
+    assert_eq!(isda.yearfrac(start, start), 0.0);
+
+    let days = (end - start).num_days() as f64;
+    assert!(
+        (days / isda.effective_year_length(start, end) - isda.yearfrac(start, end)).abs() < delta
+    );
+}
+
+#[test]
+fn test_net_signed_fraction() {
+    let conv = DayCountConvention::ActAct;
+    let (a, b) = (d(2019, 11, 3), d(2020, 8, 17));
+
+    assert!(conv.net_signed_fraction(&[(a, b), (b, a)]).abs() < 1e-12);
+    let net = conv.net_signed_fraction(&[(a, b), (d(2020, 2, 1), d(2020, 1, 1))]);
+    assert!((net - (conv.yearfrac(a, b) - 31.0 / 366.0)).abs() < 1e-12);
+    assert_eq!(conv.net_signed_fraction(&[]), 0.0);
+}
+
+#[test]
+fn test_aliases() {
+    for conv in DayCountConvention::all() {
+        let aliases = conv.aliases();
+        assert_eq!(aliases[0], conv.to_string());
+        for alias in aliases {
+            assert_eq!(DayCountConvention::from_str(alias).unwrap(), conv);
+        }
+    }
+}
+
+#[test]
+fn test_day_count_and_year_basis() {
+    let (start, end) = (d(2020, 2, 29), d(2021, 8, 31));
+
+    assert_eq!(DayCountConvention::US30360.day_count(start, end), 541.0);
+    assert_eq!(DayCountConvention::EU30360.day_count(start, end), 541.0);
+    assert_eq!(DayCountConvention::Act360.day_count(start, end), 549.0);
+    assert_eq!(DayCountConvention::ActAct.year_basis(start, end), 365.5);
+    for conv in DayCountConvention::all() {
+        assert_eq!(conv.day_count(end, start), conv.day_count(start, end));
+        assert_eq!(conv.year_basis(end, start), conv.year_basis(start, end));
+        let yf = conv.day_count(start, end) / conv.year_basis(start, end);
+        assert!((yf - conv.yearfrac(start, end)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_is_additive() {
+    let splits = [
+        (d(2019, 11, 30), d(2020, 2, 29), d(2021, 3, 31)),
+        (d(2021, 1, 15), d(2021, 1, 31), d(2021, 2, 15)),
+        (d(2020, 1, 31), d(2020, 2, 29), d(2020, 3, 31)),
+    ];
+    for conv in DayCountConvention::all() {
+        if conv.is_additive() {
+            for (a, m, c) in splits {
+                let sum = conv.yearfrac(a, m) + conv.yearfrac(m, c);
+                assert!((sum - conv.yearfrac(a, c)).abs() < 1e-12, "{conv}");
+            }
+        }
+    }
+
+    let (a, m, c) = (d(2019, 7, 1), d(2020, 1, 1), d(2020, 7, 1));
+    let act_act = DayCountConvention::ActAct;
+    assert!(!act_act.is_additive());
+    let sum = act_act.yearfrac(a, m) + act_act.yearfrac(m, c);
+    assert!((sum - act_act.yearfrac(a, c)).abs() > 1e-4);
+
+    let (a, m, c) = splits[1];
+    let us = DayCountConvention::US30360;
+    assert!(!us.is_additive());
+    assert_eq!(us.day_count(a, m) + us.day_count(m, c), 31.0);
+    assert_eq!(us.day_count(a, c), 30.0);
+}
+
+#[test]
+fn test_try_from() {
+    for b in 0..=u8::MAX {
+        let via_trait: Result<DayCountConvention, _> = b.try_into();
+        match (via_trait, DayCountConvention::from_int(b)) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b),
+            (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+            _ => panic!("TryFrom<u8> and from_int disagree on {b}"),
+        }
+    }
+    for s in [
+        "nasd30/360",
+        "act/act",
+        "act/360",
+        "act/365f",
+        "act/act/isda",
+        "",
+        "30/360",
+    ] {
+        let via_trait = DayCountConvention::try_from(s);
+        match (via_trait, DayCountConvention::from_str(s)) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b),
+            (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+            _ => panic!("TryFrom<&str> and from_str disagree on {s}"),
+        }
+    }
+}
+
+#[test]
+fn test_yearfrac_many() {
+    let start = NaiveDate::from_ymd_opt(2019, 12, 31).unwrap();
+    let pairs: Vec<(NaiveDate, NaiveDate)> = (0..500)
+        .map(|k| {
+            let s = start + chrono::Duration::days(k * 7);
+            (s, add_months(s, (k % 37) as i32 - 12))
+        })
+        .collect();
+    for conv in DayCountConvention::all() {
+        let many = conv.yearfrac_many(&pairs);
+        assert_eq!(many.len(), pairs.len());
+        for ((s, e), yf) in pairs.iter().zip(many) {
+            assert_eq!(yf, conv.yearfrac(*s, *e));
+        }
+    }
+    assert!(DayCountConvention::ActAct.yearfrac_many(&[]).is_empty());
+}
+
+#[test]
+fn test_fixed_basis() {
+    const US: Option<f64> = DayCountConvention::US30360.fixed_basis();
+    assert_eq!(US, Some(360.0));
+    assert_eq!(DayCountConvention::EU30360.fixed_basis(), Some(360.0));
+    assert_eq!(DayCountConvention::Act360.fixed_basis(), Some(360.0));
+    assert_eq!(DayCountConvention::Act365.fixed_basis(), Some(365.0));
+    assert_eq!(DayCountConvention::ActAct.fixed_basis(), None);
+    assert_eq!(DayCountConvention::ActActISDA.fixed_basis(), None);
+    assert_eq!(DayCountConvention::Bus252.fixed_basis(), Some(252.0));
+    assert_eq!(DayCountConvention::Act365_25.fixed_basis(), Some(365.25));
+
+    let start = NaiveDate::from_ymd_opt(2020, 2, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2023, 9, 15).unwrap();
+    for conv in DayCountConvention::all() {
+        if let Some(basis) = conv.fixed_basis() {
+            assert_eq!(basis, conv.year_basis(start, end));
+        }
+    }
+}
+
+#[test]
+fn test_accrued_interest() {
+    let (last_coupon, settlement) = (d(2021, 1, 31), d(2021, 5, 31));
+    let (rate, face) = (0.05, 1_000_000.0);
+    for conv in DayCountConvention::all() {
+        let yf = conv.yearfrac(last_coupon, settlement);
+        let ai = conv
+            .accrued_interest(last_coupon, settlement, rate, face)
+            .unwrap();
+        assert_eq!(ai, face * rate * yf);
+    }
+    let ai = DayCountConvention::Act360
+        .accrued_interest(last_coupon, settlement, rate, face)
+        .unwrap();
+    assert!((ai - 1_000_000.0 * 0.05 * 120.0 / 360.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_yearfrac_dt() {
+    let (start, end) = (d(2019, 12, 15), d(2020, 6, 15));
+    let start_dt = start.and_hms_opt(18, 30, 0).unwrap();
+    let end_dt = end.and_hms_opt(6, 15, 42).unwrap();
+    for conv in DayCountConvention::all() {
+        assert_eq!(
+            conv.yearfrac_dt(start_dt, end_dt),
+            conv.yearfrac(start, end)
+        );
+        assert_eq!(conv.yearfrac_dt(start, end), conv.yearfrac(start, end));
+    }
+}
+
+#[test]
+fn test_schedule_yearfracs() {
+    let conv = DayCountConvention::US30360;
+
+    let periods = conv.schedule_yearfracs(d(2020, 3, 15), d(2022, 3, 15), 6);
+    assert_eq!(periods.len(), 4);
+    assert!(periods.iter().all(|(_, _, yf)| *yf == 0.5));
+    assert_eq!(periods[3].1, d(2022, 3, 15));
+
+    // month-end issue: Aug 31 rolls to Feb 28 and back to Aug 31, stub at the end
+    let periods = conv.schedule_yearfracs(d(2020, 8, 31), d(2021, 10, 15), 6);
+    let dates: Vec<_> = periods.iter().map(|(s, e, _)| (*s, *e)).collect();
+    assert_eq!(
+        dates,
+        vec![
+            (d(2020, 8, 31), d(2021, 2, 28)),
+            (d(2021, 2, 28), d(2021, 8, 31)),
+            (d(2021, 8, 31), d(2021, 10, 15)),
+        ]
+    );
+    for (s, e, yf) in &periods {
+        assert_eq!(*yf, conv.yearfrac(*s, *e));
+    }
+    assert!(conv
+        .schedule_yearfracs(d(2020, 8, 31), d(2021, 10, 15), 0)
+        .is_empty());
+}
+
+/// Excel's YEARFRAC basis 0 day count as commonly documented, for reference
+fn excel_basis0_days(start: NaiveDate, end: NaiveDate) -> i64 {
+    use chrono::Datelike;
+    let last_of_feb = |d: NaiveDate| d.month() == 2 && d.succ_opt().unwrap().month() == 3;
+    let (mut d1, mut d2) = (start.day() as i64, end.day() as i64);
+    if d1 == 31 && d2 == 31 {
+        d1 = 30;
+        d2 = 30;
+    } else if d1 == 31 {
+        d1 = 30;
+    } else if d1 == 30 && d2 == 31 {
+        d2 = 30;
+    } else if last_of_feb(start) && last_of_feb(end) {
+        d1 = 30;
+        d2 = 30;
+    } else if last_of_feb(start) {
+        d1 = 30;
+    }
+    (end.year() - start.year()) as i64 * 360
+        + (end.month() as i64 - start.month() as i64) * 30
+        + (d2 - d1)
+}
+
+#[test]
+fn test_nasd_matches_excel_basis0() {
+    // every pair of dates around month ends over a leap and a non-leap February
+    let first = NaiveDate::from_ymd_opt(2019, 12, 25).unwrap();
+    let dates: Vec<NaiveDate> = first
+        .iter_days()
+        .take(500)
+        .filter(|d| {
+            use chrono::Datelike;
+            d.day() >= 27 || d.day() <= 2
+        })
+        .collect();
+    for start in &dates {
+        for end in dates.iter().filter(|end| *end > start) {
+            let expected = excel_basis0_days(*start, *end) as f64 / 360.0;
+            assert_eq!(
+                DayCountConvention::US30360.yearfrac(*start, *end),
+                expected,
+                "{start} -> {end}"
+            );
+            assert_eq!(
+                DayCountConvention::yearfrac_nasd(*start, *end, 0, true),
+                expected
+            );
+        }
+    }
+
+    // method 3 over-adjusts the end only when asked for
+    let (start, end) = (d(2021, 1, 15), d(2021, 2, 28));
+    assert_eq!(
+        DayCountConvention::US30360.yearfrac(start, end),
+        43.0 / 360.0
+    );
+    assert_eq!(
+        DayCountConvention::yearfrac_nasd(start, end, 3, true),
+        45.0 / 360.0
+    );
+    // without the start EOM rule a Feb 28 start stays on the 28th
+    let (start, end) = (d(2021, 2, 28), d(2021, 3, 30));
+    assert_eq!(
+        DayCountConvention::yearfrac_nasd(start, end, 0, true),
+        30.0 / 360.0
+    );
+    assert_eq!(
+        DayCountConvention::yearfrac_nasd(start, end, 0, false),
+        32.0 / 360.0
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_nasd_bad_method() {
+    let d = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    DayCountConvention::yearfrac_nasd(d, d, 4, true);
+}
+
+#[test]
+fn test_thirty360_builder() {
+    let us = Thirty360Builder::new().build();
+    assert_eq!(us, Thirty360::builder().end_of_month(true).build());
+    let dates = [
+        d(2020, 1, 31),
+        d(2020, 2, 29),
+        d(2020, 3, 31),
+        d(2021, 2, 28),
+        d(2021, 3, 30),
+        d(2021, 3, 31),
+        d(2022, 2, 28),
+        d(2022, 12, 15),
+    ];
+    for start in dates {
+        for end in dates {
+            assert_eq!(
+                us.yearfrac(start, end),
+                DayCountConvention::US30360.yearfrac(start, end)
+            );
+        }
+    }
+
+    // a Feb-28 start stays on the 28th without the end of month rule
+    let no_eom = Thirty360::builder().end_of_month(false).build();
+    assert_eq!(us.yearfrac(d(2021, 2, 28), d(2021, 3, 31)), 31.0 / 360.0);
+    assert_eq!(
+        no_eom.yearfrac(d(2021, 2, 28), d(2021, 3, 31)),
+        33.0 / 360.0
+    );
+
+    // both dates at the end of February
+    let no_feb = Thirty360::builder().eom_february(false).build();
+    assert_eq!(us.yearfrac(d(2021, 2, 28), d(2022, 2, 28)), 1.0);
+    assert_eq!(
+        no_feb.yearfrac(d(2021, 2, 28), d(2022, 2, 28)),
+        358.0 / 360.0
+    );
+}
+
+#[test]
+fn test_eq() {
+    use std::collections::HashSet;
+    assert_eq!(DayCountConvention::Act360, DayCountConvention::Act360);
+    assert_ne!(DayCountConvention::Act360, DayCountConvention::Act365);
+    assert_ne!(DayCountConvention::ActAct, DayCountConvention::ActActISDA);
+
+    let set: HashSet<DayCountConvention> = [0, 2, 2, 5, 0]
+        .into_iter()
+        .map(|b| DayCountConvention::from_byte(b).unwrap())
+        .collect();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&DayCountConvention::Act360));
+}
+
+#[test]
+fn test_all() {
+    let all = DayCountConvention::all();
+    for (i, conv) in all.iter().enumerate() {
+        assert_eq!(*conv, DayCountConvention::from_int(i as u8).unwrap());
+    }
+    assert!(DayCountConvention::from_int(all.len() as u8).is_err());
+}
+
+#[test]
+fn test_bus252() {
+    let bus = DayCountConvention::Bus252;
+    assert_eq!(DayCountConvention::from_int(6).unwrap(), bus);
+    assert_eq!(DayCountConvention::from_str("bus/252").unwrap(), bus);
+    assert_eq!(bus.family(), ConventionFamily::Business);
+
+    // Friday to the Monday three weeks later: 16 business days
+    let (start, end) = (d(2021, 4, 2), d(2021, 4, 26));
+    assert_eq!(bus.yearfrac(start, end), 16.0 / 252.0);
+    assert_eq!(yearfrac_bus252(start, end, &[]), 16.0 / 252.0);
+    assert_eq!(bus.yearfrac(end, start), 16.0 / 252.0);
+
+    // Tiradentes (Wednesday) inside, a Saturday holiday and one after the end change nothing
+    let holidays = [d(2021, 4, 21), d(2021, 4, 10), d(2021, 5, 3)];
+    assert_eq!(yearfrac_bus252(start, end, &holidays), 15.0 / 252.0);
+    // the start date itself counts, the end date does not
+    assert_eq!(yearfrac_bus252(start, end, &[start, end]), 15.0 / 252.0);
+
+    // a full year of weekdays
+    let yf = bus.yearfrac(d(2021, 1, 1), d(2022, 1, 1));
+    assert_eq!(yf, 261.0 / 252.0);
+    assert_eq!(bus.integrate(start, end, |_| 1.0), bus.yearfrac(start, end));
+
+    // a holiday listed twice is skipped once
+    let twice = [d(2021, 4, 21), d(2021, 4, 21)];
+    assert_eq!(yearfrac_bus252(start, end, &twice), 15.0 / 252.0);
+
+    // matches walking the days, from every weekday and for every leftover length
+    let holidays = [d(2021, 4, 7), d(2021, 4, 17)];
+    for first in start.iter_days().take(7) {
+        for last in first.iter_days().take(30) {
+            let walked = first
+                .iter_days()
+                .take_while(|day| *day < last)
+                .filter(|day| is_business_day(*day, &holidays))
+                .count();
+            assert_eq!(
+                yearfrac_bus252(first, last, &holidays),
+                walked as f64 / 252.0
+            );
+        }
+    }
+
+    // chrono's whole date range, 5 weekdays in each whole week
+    let weeks = (NaiveDate::MAX - NaiveDate::MIN).num_days() / 7;
+    let leftover = bus.yearfrac(NaiveDate::MIN, NaiveDate::MAX) * 252.0 - (weeks * 5) as f64;
+    assert!((0.0..7.0).contains(&leftover));
+}
+
+#[test]
+fn test_adjusted_days() {
+    // both ends on the 31st: rolled to 30 under 30/360
+    let (start, end) = (d(2021, 5, 31), d(2021, 8, 31));
+    assert_eq!(DayCountConvention::US30360.adjusted_days(start, end), 90);
+    assert_eq!(DayCountConvention::EU30360.adjusted_days(start, end), 90);
+    assert_eq!(DayCountConvention::Act360.adjusted_days(start, end), 92);
+    assert_eq!(DayCountConvention::US30360.adjusted_days(end, start), -90);
+
+    // no adjustment
+    let (start, end) = (d(2021, 5, 10), d(2021, 8, 20));
+    for conv in [DayCountConvention::US30360, DayCountConvention::EU30360] {
+        assert_eq!(conv.adjusted_days(start, end), 100);
+    }
+    for conv in [
+        DayCountConvention::ActAct,
+        DayCountConvention::Act360,
+        DayCountConvention::Act365,
+        DayCountConvention::ActActISDA,
+    ] {
+        assert_eq!(conv.adjusted_days(start, end), 102);
+        assert_eq!(conv.adjusted_days(end, start), -102);
+    }
+    assert_eq!(DayCountConvention::Act365.adjusted_days(start, start), 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_tokens() {
+    for conv in DayCountConvention::all() {
+        let json = serde_json::to_string(&conv).unwrap();
+        assert_eq!(json, format!("\"{conv}\""));
+        assert_eq!(
+            serde_json::from_str::<DayCountConvention>(&json).unwrap(),
+            conv
+        );
+        for alias in conv.aliases() {
+            let de: DayCountConvention = serde_json::from_str(&format!("\"{alias}\"")).unwrap();
+            assert_eq!(de, conv);
+        }
+    }
+    assert!(serde_json::from_str::<DayCountConvention>("\"US30360\"").is_err());
+}
+
+#[test]
+fn test_yearfrac_icma() {
+    let (period_start, period_end) = (d(2021, 8, 15), d(2022, 2, 15));
+
+    let full = yearfrac_icma(period_start, period_end, period_start, period_end, 2);
+    assert_eq!(full, 0.5);
+    let annual = yearfrac_icma(
+        d(2020, 2, 29),
+        d(2021, 2, 28),
+        d(2020, 2, 29),
+        d(2021, 2, 28),
+        1,
+    );
+    assert_eq!(annual, 1.0);
+
+    // 46 of the 184 days in the period
+    let partial = yearfrac_icma(period_start, d(2021, 9, 30), period_start, period_end, 2);
+    assert!((partial - 46.0 / (2.0 * 184.0)).abs() < 1e-12);
+    let reversed = yearfrac_icma(d(2021, 9, 30), period_start, period_start, period_end, 2);
+    assert_eq!(reversed, partial);
+}
+
+#[test]
+#[should_panic]
+fn test_yearfrac_icma_outside_period() {
+    yearfrac_icma(
+        d(2021, 8, 1),
+        d(2021, 9, 30),
+        d(2021, 8, 15),
+        d(2022, 2, 15),
+        2,
+    );
+}
+
+#[test]
+fn test_from_str_suggestion() {
+    let err = DayCountConvention::from_str("act/365").unwrap_err();
+    assert!(matches!(err, DayCountConventionError::InvalidValue { ref val } if val == "act/365"));
+    assert!(err.to_string().contains("Did you mean act365?"));
+
+    let err = DayCountConvention::from_str("eur30360").unwrap_err();
+    assert!(err.to_string().contains("Did you mean eur30/360?"));
+    let err = DayCountConvention::from_str("30/360").unwrap_err();
+    assert!(!err.to_string().contains("Did you mean"));
+    let err = DayCountConvention::from_int(9).unwrap_err();
+    assert!(!err.to_string().contains("Did you mean"));
+}
+
+#[test]
+fn test_from_str_normalized() {
+    assert_eq!(
+        DayCountConvention::from_str("ACT/ACT").unwrap(),
+        DayCountConvention::ActAct
+    );
+    assert_eq!(
+        DayCountConvention::from_str(" act360 ").unwrap(),
+        DayCountConvention::Act360
+    );
+    assert_eq!(
+        DayCountConvention::from_str("Nasd30/360").unwrap(),
+        DayCountConvention::US30360
+    );
+    assert_eq!(
+        DayCountConvention::from_str("\tActual/365 Fixed\n").unwrap(),
+        DayCountConvention::Act365
+    );
+
+    let err = DayCountConvention::from_str(" ACT/365 ").unwrap_err();
+    assert!(matches!(err, DayCountConventionError::InvalidValue { ref val } if val == " ACT/365 "));
+    assert!(err.to_string().contains("Did you mean act365?"));
+}
+
+#[test]
+fn test_yearfrac_ratio() {
+    let pairs = [
+        (d(1978, 2, 28), d(2020, 5, 17)),
+        (d(2019, 12, 15), d(2020, 6, 15)),
+        (d(2020, 2, 29), d(2021, 2, 28)),
+        (d(2021, 1, 31), d(2021, 3, 31)),
+        (d(2019, 7, 1), d(2022, 3, 1)),
+        (d(2021, 3, 1), d(2021, 3, 1)),
+    ];
+    for conv in DayCountConvention::all() {
+        for (start, end) in pairs {
+            let (num, den) = conv.yearfrac_ratio(start, end);
+            assert!(den > 0);
+            assert!(
+                (num as f64 / den as f64 - conv.yearfrac(start, end)).abs() < 1e-12,
+                "{conv}"
+            );
+            assert_eq!(conv.yearfrac_ratio(end, start), (num, den));
+        }
+    }
+    assert_eq!(
+        DayCountConvention::US30360.yearfrac_ratio(d(2021, 1, 31), d(2021, 3, 31)),
+        (1, 6)
+    );
+    // 3 years averaging 365.33 days
+    assert_eq!(
+        DayCountConvention::ActAct.yearfrac_ratio(d(2019, 7, 1), d(2021, 7, 1)),
+        (731 * 3, 1096)
+    );
+    assert_eq!(
+        DayCountConvention::Act365.yearfrac_ratio(d(2021, 3, 1), d(2021, 3, 1)),
+        (0, 1)
+    );
+}
+
+#[test]
+fn test_days_in_year() {
+    assert_eq!(days_in_year(2000), 366);
+    assert_eq!(days_in_year(1900), 365);
+    assert_eq!(days_in_year(2020), 366);
+    assert_eq!(days_in_year(2021), 365);
+}
+
+#[test]
+fn test_days_in_month() {
+    assert_eq!(days_in_month(2, 2020), 29);
+    assert_eq!(days_in_month(2, 2021), 28);
+    assert_eq!(days_in_month(2, 1900), 28);
+    assert_eq!(days_in_month(4, 2021), 30);
+    assert_eq!(days_in_month(7, 2021), 31);
+    let total: u32 = (1..=12).map(|m| days_in_month(m, 2020)).sum();
+    assert_eq!(total, days_in_year(2020));
+}
+
+#[test]
+#[should_panic]
+fn test_days_in_month_invalid() {
+    days_in_month(13, 2021);
+}
+
+#[test]
+fn test_try_is_end_of_month() {
+    assert_eq!(try_is_end_of_month(28, 0, 2021), None);
+    assert_eq!(try_is_end_of_month(28, 13, 2021), None);
+    // the unchecked version falls back to February
+    assert!(is_end_of_month(28, 13, 2021));
+
+    assert_eq!(try_is_end_of_month(28, 2, 2021), Some(true));
+    assert_eq!(try_is_end_of_month(28, 2, 2020), Some(false));
+    assert_eq!(try_is_end_of_month(30, 6, 2020), Some(true));
+    assert_eq!(try_is_end_of_month(30, 7, 2020), Some(false));
+    for month in 1..=12 {
+        for day in 1..=31 {
+            assert_eq!(
+                try_is_end_of_month(day, month, 2024),
+                Some(is_end_of_month(day, month, 2024))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_coupon_yearfrac() {
+    let per_year: Vec<u32> = [
+        Frequency::Annual,
+        Frequency::SemiAnnual,
+        Frequency::Quarterly,
+        Frequency::Monthly,
+    ]
+    .iter()
+    .map(Frequency::per_year)
+    .collect();
+    assert_eq!(per_year, vec![1, 2, 4, 12]);
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::EU30360,
+        DayCountConvention::ActAct,
+        DayCountConvention::ActActISDA,
+        DayCountConvention::Bus252,
+    ] {
+        assert_eq!(conv.coupon_yearfrac(Frequency::Annual), 1.0);
+        assert_eq!(conv.coupon_yearfrac(Frequency::SemiAnnual), 0.5);
+        assert_eq!(conv.coupon_yearfrac(Frequency::Quarterly), 0.25);
+    }
+    // a nominal 365.25 day year over the basis
+    let act360 = DayCountConvention::Act360;
+    assert_eq!(
+        act360.coupon_yearfrac(Frequency::SemiAnnual),
+        365.25 / 720.0
+    );
+    assert_eq!(
+        act360.coupon_yearfrac(Frequency::Quarterly),
+        365.25 / 1440.0
+    );
+    let act365 = DayCountConvention::Act365;
+    assert_eq!(act365.coupon_yearfrac(Frequency::Annual), 365.25 / 365.0);
+    let act365_25 = DayCountConvention::Act365_25;
+    assert_eq!(act365_25.coupon_yearfrac(Frequency::Annual), 1.0);
+    assert_eq!(act365_25.coupon_yearfrac(Frequency::SemiAnnual), 0.5);
+    assert_eq!(act365_25.coupon_yearfrac(Frequency::Quarterly), 0.25);
+    assert!(act360.coupon_yearfrac(Frequency::Monthly) > 1.0 / 12.0);
+}
+
+#[test]
+fn test_yearfrac_ymd() {
+    let yf = yearfrac_ymd(1, 1978, 2, 28, 2020, 5, 17);
+    assert!((yf - 42.21424933147).abs() < 1e-9);
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    assert_eq!(yf, DayCountConvention::ActAct.yearfrac(start, end));
+}
+
+#[test]
+#[should_panic]
+fn test_yearfrac_ymd_bad_date() {
+    yearfrac_ymd(1, 2021, 2, 29, 2022, 1, 1);
+}
+
+#[test]
+fn test_is_leap_year_proleptic() {
+    assert!(is_leap_year(0));
+    assert!(is_leap_year(-4));
+    assert!(!is_leap_year(-100));
+    assert!(is_leap_year(-400));
+    assert!(!is_leap_year(-1));
+    for year in -800..=800 {
+        let leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+        assert_eq!(is_leap_year(year), leap, "year {year}");
+    }
+}
+
+#[test]
+fn test_act365_25() {
+    let conv = DayCountConvention::Act365_25;
+    assert_eq!(DayCountConvention::from_int(7).unwrap(), conv);
+    assert_eq!(DayCountConvention::from_str("act/365.25").unwrap(), conv);
+    assert_eq!(conv.to_string(), "act/365.25");
+    // 15419 actual days
+    let yf = conv.yearfrac(d(1978, 2, 28), d(2020, 5, 17));
+    assert!((yf - 42.21492128679).abs() < 1e-9);
+    // four years including one leap day are exactly 4
+    assert_eq!(conv.yearfrac(d(2019, 1, 1), d(2023, 1, 1)), 4.0);
+    assert_eq!(
+        conv.yearfrac_ratio(d(2021, 1, 1), d(2021, 7, 2)),
+        (728, 1461)
+    );
+}
+
+#[test]
+fn test_yearfrac_f32() {
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    for conv in DayCountConvention::all() {
+        let single = conv.yearfrac_f32(start, end) as f64;
+        let double = conv.yearfrac(start, end);
+        assert!(
+            (single - double).abs() < 1e-5,
+            "{conv}: {single} vs {double}"
+        );
+        assert_eq!(conv.yearfrac_f32(end, start) as f64, single);
+        assert_eq!(conv.yearfrac_f32(start, start), 0.0);
+    }
+}
+
+#[test]
+fn test_compare_all() {
+    let start = NaiveDate::from_ymd_opt(1978, 2, 28).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+    let report = DayCountConvention::compare_all(start, end);
+    assert_eq!(report.len(), DayCountConvention::all().len());
+    // the five Excel conventions come first
+    assert!((report[0].1 - 42.21388888889).abs() < 1e-9);
+    assert!((report[4].1 - 42.21944444444).abs() < 1e-9);
+    let act360 = report
+        .iter()
+        .find(|(conv, _)| *conv == DayCountConvention::Act360)
+        .unwrap();
+    assert_eq!(act360.1, DayCountConvention::Act360.yearfrac(start, end));
+}
+
+#[test]
+fn test_to_int() {
+    for (code, conv) in DayCountConvention::all().into_iter().enumerate() {
+        assert_eq!(conv.to_int() as usize, code);
+        assert_eq!(DayCountConvention::from_int(conv.to_int()).unwrap(), conv);
+        assert_eq!(conv.to_byte(), conv.to_int());
+    }
+}
+
+#[test]
+fn test_custom_convention() {
+    let act360 = CustomConvention::new(Numerator::Actual, 360.0).unwrap();
+    let eu30360 = CustomConvention::new(Numerator::Eu30360, 360.0).unwrap();
+    let us30360 = CustomConvention::new(Numerator::Us30360, 360.0).unwrap();
+    let dates = [
+        d(1978, 2, 28),
+        d(2020, 2, 29),
+        d(2020, 5, 17),
+        d(2021, 1, 31),
+        d(2021, 2, 28),
+        d(2021, 3, 31),
+    ];
+    for start in dates {
+        for end in dates {
+            assert_eq!(
+                act360.yearfrac(start, end),
+                DayCountConvention::Act360.yearfrac(start, end)
+            );
+            assert_eq!(
+                eu30360.yearfrac(start, end),
+                DayCountConvention::EU30360.yearfrac(start, end)
+            );
+            assert_eq!(
+                us30360.yearfrac(start, end),
+                DayCountConvention::US30360.yearfrac(start, end)
+            );
+        }
+    }
+
+    for basis in [0.0, -360.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            CustomConvention::new(Numerator::Actual, basis),
+            Err(DayCountConventionError::InvalidInput { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_simple_and_continuous_discount_factors() {
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2022, 7, 1).unwrap();
+    let conv = DayCountConvention::Act365;
+    assert_eq!(conv.simple_discount_factor(start, end, 0.0).unwrap(), 1.0);
+    assert_eq!(
+        conv.continuous_discount_factor(start, end, 0.0).unwrap(),
+        1.0
+    );
+
+    let yf = conv.yearfrac(start, end);
+    let simple = conv.simple_discount_factor(start, end, 0.03).unwrap();
+    assert!((simple - 1.0 / (1.0 + 0.03 * yf)).abs() < 1e-15);
+    let continuous = conv.continuous_discount_factor(start, end, 0.03).unwrap();
+    assert!((continuous - (-0.03 * yf).exp()).abs() < 1e-15);
+
+    assert!(matches!(
+        conv.simple_discount_factor(start, end, f64::INFINITY),
+        Err(DayCountConventionError::NonFiniteInput { .. })
+    ));
+}
+
+#[test]
+fn test_same_date_is_zero() {
+    let dates = [
+        d(2020, 1, 1),
+        d(2020, 2, 29),
+        d(2021, 2, 28),
+        d(2021, 1, 31),
+        d(2021, 4, 30),
+        d(2021, 12, 31),
+        d(2021, 5, 15),
+    ];
+    for conv in DayCountConvention::all() {
+        for date in dates {
+            let yf = conv.yearfrac(date, date);
+            assert_eq!(yf, 0.0, "{conv} {date}");
+            assert!(yf.is_sign_positive(), "{conv} {date}");
+            assert_eq!(conv.yearfrac_f32(date, date), 0.0, "{conv} {date}");
+        }
+    }
+}
+
+#[test]
+fn test_thirty360_feb_29() {
+    let us = DayCountConvention::US30360;
+    let eu = DayCountConvention::EU30360;
+    // Excel YEARFRAC(..., 0) and YEARFRAC(..., 4)
+    let cases = [
+        (d(2020, 2, 29), d(2020, 3, 31), 31.0, 31.0),
+        (d(2020, 2, 29), d(2020, 8, 29), 179.0, 180.0),
+        (d(2020, 2, 29), d(2021, 2, 28), 360.0, 359.0),
+        (d(2020, 2, 29), d(2024, 2, 29), 1440.0, 1440.0),
+        (d(2020, 2, 15), d(2020, 2, 29), 14.0, 14.0),
+        (d(2019, 2, 28), d(2020, 2, 29), 360.0, 361.0),
+    ];
+    for (start, end, us_days, eu_days) in cases {
+        assert_eq!(us.yearfrac(start, end), us_days / 360.0, "{start} -> {end}");
+        assert_eq!(eu.yearfrac(start, end), eu_days / 360.0, "{start} -> {end}");
+    }
+}
+
+#[test]
+fn test_from_excel_basis() {
+    for basis in 0..5 {
+        assert_eq!(
+            DayCountConvention::from_excel_basis(basis).unwrap(),
+            DayCountConvention::from_int(basis).unwrap()
+        );
+    }
+    for basis in [5, 6, 7, 8, u8::MAX] {
+        let err = DayCountConvention::from_excel_basis(basis).unwrap_err();
+        assert!(matches!(err, DayCountConventionError::InvalidExcelBasis { val } if val == basis));
+        assert!(err.to_string().contains("range 0-4"));
+        assert!(!err.to_string().contains("0-7"));
+    }
+}
+
+#[test]
+fn test_yearfrac_checked() {
+    let pairs = [
+        (d(1978, 2, 28), d(2020, 5, 17)),
+        (d(2020, 5, 17), d(1978, 2, 28)),
+        (d(2020, 2, 29), d(2021, 2, 28)),
+        (d(2021, 3, 1), d(2021, 3, 1)),
+    ];
+    for conv in DayCountConvention::all() {
+        for (start, end) in pairs {
+            assert_eq!(
+                conv.yearfrac_checked(start, end).unwrap(),
+                conv.yearfrac(start, end)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_business_day_adjustment() {
+    let saturday = d(2021, 3, 6);
+    assert_eq!(
+        adjust(saturday, BusinessDayConvention::Following, &[]),
+        d(2021, 3, 8)
+    );
+    assert_eq!(
+        adjust(saturday, BusinessDayConvention::Preceding, &[]),
+        d(2021, 3, 5)
+    );
+    // a holiday on the following Monday pushes it further
+    assert_eq!(
+        adjust(saturday, BusinessDayConvention::Following, &[d(2021, 3, 8)]),
+        d(2021, 3, 9)
+    );
+    // business days stay put
+    for bdc in [
+        BusinessDayConvention::Following,
+        BusinessDayConvention::ModifiedFollowing,
+        BusinessDayConvention::Preceding,
+    ] {
+        assert_eq!(adjust(d(2021, 3, 5), bdc, &[]), d(2021, 3, 5));
+    }
+
+    // Friday Apr 30 is a holiday: following lands in May, so modified following rolls back
+    let month_end_friday = d(2021, 4, 30);
+    let holidays = [month_end_friday];
+    assert_eq!(
+        adjust(
+            month_end_friday,
+            BusinessDayConvention::Following,
+            &holidays
+        ),
+        d(2021, 5, 3)
+    );
+    assert_eq!(
+        adjust(
+            month_end_friday,
+            BusinessDayConvention::ModifiedFollowing,
+            &holidays
+        ),
+        d(2021, 4, 29)
+    );
+    // within the month modified following is plain following
+    assert_eq!(
+        adjust(saturday, BusinessDayConvention::ModifiedFollowing, &[]),
+        d(2021, 3, 8)
+    );
+
+    let yf = DayCountConvention::Act365.yearfrac_adjusted(
+        saturday,
+        month_end_friday,
+        BusinessDayConvention::ModifiedFollowing,
+        &holidays,
+    );
+    assert_eq!(
+        yf,
+        DayCountConvention::Act365.yearfrac(d(2021, 3, 8), d(2021, 4, 29))
+    );
+}
+
+#[test]
+fn test_nominal_basis() {
+    assert_eq!(DayCountConvention::Act360.nominal_basis(), 360.0);
+    assert_eq!(DayCountConvention::Act365.nominal_basis(), 365.0);
+    assert_eq!(DayCountConvention::US30360.nominal_basis(), 360.0);
+    assert_eq!(DayCountConvention::Bus252.nominal_basis(), 252.0);
+    assert_eq!(DayCountConvention::ActAct.nominal_basis(), 365.25);
+    assert_eq!(DayCountConvention::ActActISDA.nominal_basis(), 365.25);
+    for conv in DayCountConvention::all() {
+        if let Some(basis) = conv.fixed_basis() {
+            assert_eq!(conv.nominal_basis(), basis);
+        }
+    }
+}
+
+#[test]
+fn test_periods_between() {
+    let conv = DayCountConvention::US30360;
+    assert_eq!(
+        conv.periods_between(d(2021, 1, 15), d(2022, 1, 15), Frequency::SemiAnnual),
+        (2, 0.0)
+    );
+    assert_eq!(
+        conv.periods_between(d(2022, 1, 15), d(2021, 1, 15), Frequency::SemiAnnual),
+        (2, 0.0)
+    );
+    assert_eq!(
+        conv.periods_between(d(2021, 1, 15), d(2022, 3, 15), Frequency::SemiAnnual),
+        (2, 60.0 / 360.0)
+    );
+    assert_eq!(
+        conv.periods_between(d(2021, 1, 15), d(2021, 5, 1), Frequency::SemiAnnual),
+        (0, conv.yearfrac(d(2021, 1, 15), d(2021, 5, 1)))
+    );
+    // rolled from the start, Sep 30 is followed by Oct 31, not Oct 30
+    let act = DayCountConvention::Act365;
+    let (periods, stub) = act.periods_between(d(2021, 8, 31), d(2021, 11, 15), Frequency::Monthly);
+    assert_eq!(periods, 2);
+    assert_eq!(stub, 15.0 / 365.0);
+    let (periods, stub) = act.periods_between(d(2021, 1, 31), d(2021, 3, 31), Frequency::Monthly);
+    assert_eq!((periods, stub), (2, 0.0));
+}
+
+#[test]
+fn test_thirty360_extreme_years() {
+    use chrono::Datelike;
+    // chrono's whole range, over half a million years apart
+    let (min, max) = (NaiveDate::MIN, NaiveDate::MAX);
+    let mid = NaiveDate::from_ymd_opt(0, 6, 15).unwrap();
+    for conv in [
+        DayCountConvention::US30360,
+        DayCountConvention::EU30360,
+        DayCountConvention::Act360,
+    ] {
+        let whole = conv.yearfrac(min, max);
+        assert!(whole.is_finite() && whole > 500_000.0, "{conv}: {whole}");
+        let first = conv.yearfrac(min, mid);
+        assert!(first > 0.0 && first < whole, "{conv}");
+        assert!(conv.yearfrac_signed(max, min) < 0.0, "{conv}");
+    }
+    assert_eq!(
+        DayCountConvention::yearfrac_accounting_360(min, max),
+        ((max.year() as i64 - min.year() as i64) * 360 + 11 * 30 + 30) as f64 / 360.0
+    );
+}
+
+#[test]
+fn test_short_name_and_description() {
+    for conv in DayCountConvention::all() {
+        assert_eq!(
+            DayCountConvention::from_str(conv.short_name()).unwrap(),
+            conv
+        );
+        assert_eq!(conv.short_name(), conv.to_string());
+        assert!(!conv.description().is_empty());
+    }
+    assert_eq!(
+        DayCountConvention::US30360.description(),
+        "US (NASD) 30/360 Bond Basis"
+    );
+}
+
+#[test]
+fn test_actual_days() {
+    let pairs = [
+        (d(1978, 2, 28), d(2020, 5, 17)),
+        (d(2020, 2, 29), d(2021, 2, 28)),
+        (d(2021, 3, 1), d(2021, 3, 1)),
+    ];
+    for conv in DayCountConvention::all() {
+        for (start, end) in pairs {
+            assert_eq!(conv.actual_days(start, end), (end - start).num_days());
+            assert_eq!(conv.actual_days(end, start), (end - start).num_days());
+        }
+    }
+    let (start, end) = pairs[0];
+    assert_eq!(
+        DayCountConvention::Act360.actual_days(start, end) as f64 / 360.0,
+        DayCountConvention::Act360.yearfrac(start, end)
+    );
+}
+
+#[test]
+fn test_possible_values() {
+    let values = DayCountConvention::possible_values();
+    assert_eq!(values.len(), DayCountConvention::all().len());
+    for (value, conv) in values.iter().zip(DayCountConvention::all()) {
+        assert_eq!(DayCountConvention::from_str(value).unwrap(), conv);
+        assert_eq!(*value, conv.short_name());
+        assert_eq!(*value, conv.to_string());
+        assert_eq!(*value, conv.aliases()[0]);
+        for alias in conv.aliases() {
+            assert_eq!(DayCountConvention::from_str(alias).unwrap(), conv);
+        }
+    }
+    let err = DayCountConvention::from_str("nope")
+        .unwrap_err()
+        .to_string();
+    for value in values {
+        assert!(err.contains(value), "{err}");
+    }
+    assert!(err.contains(&format!("0-{}", values.len() - 1)), "{err}");
+}
+
+#[test]
+fn test_cumulative_yearfracs() {
+    let dates = [d(2021, 1, 31), d(2021, 2, 28), d(2021, 3, 31)];
+    let conv = DayCountConvention::US30360;
+    assert_eq!(
+        conv.cumulative_yearfracs(&dates),
+        vec![0.0, 28.0 / 360.0, 60.0 / 360.0]
+    );
+    // US30360 is not additive: 28 + 31 days when summing consecutive periods
+    let summed: f64 = dates.windows(2).map(|p| conv.yearfrac(p[0], p[1])).sum();
+    assert_eq!(summed, 59.0 / 360.0);
+
+    assert!(conv.cumulative_yearfracs(&[]).is_empty());
+    assert_eq!(conv.cumulative_yearfracs(&dates[..1]), vec![0.0]);
+}
+
+#[test]
+#[should_panic]
+fn test_cumulative_yearfracs_unsorted() {
+    DayCountConvention::Act360.cumulative_yearfracs(&[d(2021, 3, 1), d(2021, 1, 1)]);
+}
+
+#[test]
+fn test_is_business_day() {
+    let saturday = d(2021, 3, 6);
+    let sunday = d(2021, 3, 7);
+    let wednesday = d(2021, 3, 3);
+    assert!(!is_business_day(saturday, &[]));
+    assert!(!is_business_day(sunday, &[]));
+    assert!(is_business_day(wednesday, &[]));
+    assert!(!is_business_day(wednesday, &[d(2021, 1, 1), wednesday]));
+    assert!(is_business_day(wednesday, &[d(2021, 1, 1)]));
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+use proptest::prelude::*;
+use yearfrac::DayCountConvention;
+
+/// Dates from 1700 to 2300, so both pre-1900 and post-2100 centuries are covered.
+fn date() -> impl Strategy<Value = NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(1700, 1, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2300, 12, 31).unwrap();
+    (0..=(last - first).num_days()).prop_map(move |days| first + chrono::Duration::days(days))
+}
+
+/// Dates within a few days of a Feb 28 / Feb 29 / Mar 1 boundary.
+fn leap_boundary_date() -> impl Strategy<Value = NaiveDate> {
+    (1700..=2300i32, -3..=3i64).prop_map(|(year, offset)| {
+        NaiveDate::from_ymd_opt(year, 3, 1).unwrap() + chrono::Duration::days(offset)
+    })
+}
+
+fn convention() -> impl Strategy<Value = DayCountConvention> {
+    prop::sample::select(DayCountConvention::all().to_vec())
+}
+
+fn any_date() -> impl Strategy<Value = NaiveDate> {
+    prop_oneof![date(), leap_boundary_date()]
+}
+
+proptest! {
+    #[test]
+    fn yearfrac_is_non_negative(conv in convention(), a in any_date(), b in any_date()) {
+        prop_assert!(conv.yearfrac(a, b) >= 0.0);
+    }
+
+    #[test]
+    fn yearfrac_is_symmetric(conv in convention(), a in any_date(), b in any_date()) {
+        prop_assert_eq!(conv.yearfrac(a, b), conv.yearfrac(b, a));
+    }
+
+    #[test]
+    fn yearfrac_signed_is_antisymmetric(conv in convention(), a in any_date(), b in any_date()) {
+        prop_assert_eq!(conv.yearfrac_signed(a, b), -conv.yearfrac_signed(b, a));
+    }
+
+    #[test]
+    fn yearfrac_of_same_date_is_zero(conv in convention(), a in any_date()) {
+        prop_assert_eq!(conv.yearfrac(a, a), 0.0);
+        prop_assert_eq!(conv.yearfrac_signed(a, a), 0.0);
+    }
+}